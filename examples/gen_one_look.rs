@@ -0,0 +1,29 @@
+//! Writes the algorithms of every last layer case with every face turn, the table coming
+//! with the library as `src/solver/one_look/one_look_htm.tbl`:
+//!
+//! ```sh
+//! cargo run --release --no-default-features --example gen_one_look -- <dir>
+//! ```
+//!
+//! The table and the pattern tables it is searched with land in `<dir>`, `one_look` by
+//! default. Built without the `one-look-table` feature, no case is known beforehand.
+use std::time::Instant;
+
+use rubik::{
+    solver::{
+        last_layer::LlSet,
+        one_look::{LlMoves, OneLookSolver},
+    },
+    tables::TableCache,
+};
+
+fn main() {
+    let dir = std::env::args().nth(1).unwrap_or("one_look".to_string());
+    let start = Instant::now();
+    let solver = OneLookSolver::with_cache(LlMoves::Htm, 16, TableCache::new(&dir));
+    let solved = solver.precompute(LlSet::OneLook);
+    println!(
+        "{solved} cases in {:?}, written to {dir}/one_look_htm.tbl",
+        start.elapsed()
+    );
+}
@@ -1,7 +1,10 @@
 use rubik::{
     prelude::*,
     solver::{
+        beginner::BeginnerSolver,
+        cfop::CfopSolver,
         ida_star::IdaStarSolver,
+        roux::RouxSolver,
         sa::SaRubikSolver,
         thistlethwaite::{BfsSolver, Thistlethwaite},
         RubikSolver,
@@ -102,6 +105,24 @@ pub fn main() {
                 "G1" => BfsSolver::G1.solve(rubik),
                 "G2" => BfsSolver::G2.solve(rubik),
                 "G3" => BfsSolver::G3.solve(rubik),
+                "BEGINNER" => {
+                    for step in BeginnerSolver.steps(rubik.clone()) {
+                        println!("{step}");
+                    }
+                    BeginnerSolver.solve(rubik)
+                }
+                "CFOP" => {
+                    for step in CfopSolver.steps(rubik.clone()) {
+                        println!("{step}");
+                    }
+                    CfopSolver.solve(rubik)
+                }
+                "ROUX" => {
+                    for step in RouxSolver.steps(rubik.clone()) {
+                        println!("{step}");
+                    }
+                    RouxSolver.solve(rubik)
+                }
                 solver => {
                     println!("no such solver {solver}");
                     input.clear();
@@ -0,0 +1,283 @@
+//! Algorithm databases: last layer algorithm sets read from plain text files, checked
+//! against the cases they claim to solve.
+//!
+//! The format is line based, blank lines and lines starting with `#` being ignored:
+//!
+//! ```text
+//! [PLL]
+//! case T perm
+//!   alg R U R' U' R' F R2 U' R' U' R U R' F'
+//!   alg (R U R' U')(R' F R2 U')(R' U' R U R' F')
+//!   tags 3-gen, popular
+//! ```
+//!
+//! `[name]` starts a set, `case name` a case of the current set, each `alg` line adds
+//! an algorithm of the current case in Singmaster notation, and `tags` lists words
+//! describing the case, separated by commas.
+//!
+//! Every algorithm must solve its case from a cube with the first two layers solved
+//! on D. The cases of the sets named `OLL`, `PLL`, `COLL` and `ZBLL` are compared up
+//! to their part of the last layer and must be named as [`recognize_ll`] names them,
+//! as `OLL 27`, `OLL 27 / Sune`, `T perm`, `PLL T` or `COLL S 3`. COLL and ZBLL cases
+//! are numbered by this library,
+//! [`LlSet::named_cases`](crate::solver::last_layer::LlSet::named_cases) listing every
+//! name with an example of its case to draw with [`crate::render::svg::last_layer`].
+use std::{error::Error, fmt::Display, path::Path};
+
+use crate::{
+    parser::singmaster::parse,
+    prelude::RubikLayerTransform as T,
+    solver::{
+        ergonomics::Ergonomics,
+        last_layer::{recognize_ll, recognize_oll, recognize_pll, Case, LlState},
+        steps::{invert, Metric},
+    },
+    Rubik,
+};
+
+/// An algorithm as written in the database, with its moves.
+#[derive(Debug, Clone)]
+pub struct DbAlg {
+    pub notation: String,
+    pub moves: Vec<&'static T>,
+}
+
+impl DbAlg {
+    /// the last layer case the algorithm solves, `None` if it breaks the first two layers
+    fn case(&self) -> Option<Rubik> {
+        let mut rubik = Rubik::new();
+        invert(&self.moves)
+            .iter()
+            .for_each(|op| op.apply_on(&mut rubik));
+        LlState::new(&rubik).map(|_| rubik)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct AlgCase {
+    pub name: String,
+    pub algs: Vec<DbAlg>,
+    pub tags: Vec<String>,
+}
+
+impl AlgCase {
+    /// the algorithms of the case, the fastest to play first
+    pub fn ranked(&self, ergonomics: &Ergonomics) -> Vec<&DbAlg> {
+        ergonomics.rank(&self.algs, |alg| &alg.moves)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct AlgSet {
+    pub name: String,
+    pub cases: Vec<AlgCase>,
+}
+
+impl AlgSet {
+    /// the part of the last layer the algorithms of the set take care of
+    fn key(&self) -> fn(LlState) -> LlState {
+        match self.name.as_str() {
+            "OLL" => LlState::oll_key,
+            "PLL" => LlState::pll_key,
+            "COLL" => LlState::coll_key,
+            _ => LlState::zbll_key,
+        }
+    }
+
+    /// whether `rubik`, with the first two layers solved, is `case` for this set
+    fn names(&self, rubik: &Rubik, case: &str) -> bool {
+        let labels = |case: Case| vec![case.alg.name.to_string(), case.label()];
+        let names = match self.name.as_str() {
+            "OLL" => recognize_oll(rubik).map(labels),
+            "PLL" => recognize_pll(rubik).map(labels),
+            "COLL" => recognize_ll(rubik)
+                .and_then(|ll| ll.coll)
+                .map(|name| vec![name]),
+            "ZBLL" => recognize_ll(rubik)
+                .and_then(|ll| ll.zbll)
+                .map(|name| vec![name]),
+            _ => return true,
+        };
+        names.is_some_and(|names| names.iter().any(|name| name == case))
+    }
+}
+
+/// A problem in a file or in an algorithm of a database.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AlgDbError {
+    /// a line that could not be read, numbered from 1
+    Syntax { line: usize, message: String },
+    /// the algorithm does not keep the first two layers
+    BreaksF2l {
+        set: String,
+        case: String,
+        alg: String,
+    },
+    /// the algorithm solves another case than the first algorithm of its case
+    Mismatch {
+        set: String,
+        case: String,
+        alg: String,
+    },
+    /// the case solved by the algorithm goes by another name
+    Misnamed {
+        set: String,
+        case: String,
+        alg: String,
+    },
+}
+
+impl Display for AlgDbError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AlgDbError::Syntax { line, message } => write!(f, "line {line}: {message}"),
+            AlgDbError::BreaksF2l { set, case, alg } => {
+                write!(f, "{set} {case}: {alg} breaks the first two layers")
+            }
+            AlgDbError::Mismatch { set, case, alg } => {
+                write!(f, "{set} {case}: {alg} solves another case")
+            }
+            AlgDbError::Misnamed { set, case, alg } => {
+                write!(f, "{set} {case}: {alg} solves a case of another name")
+            }
+        }
+    }
+}
+
+impl Error for AlgDbError {}
+
+/// Algorithm sets, each made of named cases.
+#[derive(Debug, Clone, Default)]
+pub struct AlgDb {
+    pub sets: Vec<AlgSet>,
+}
+
+impl AlgDb {
+    /// reads a database in the format of the module documentation, without verifying it
+    pub fn parse(src: &str) -> Result<Self, AlgDbError> {
+        let mut db = AlgDb::default();
+        for (index, line) in src.lines().enumerate() {
+            let syntax = |message: String| AlgDbError::Syntax {
+                line: index + 1,
+                message,
+            };
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                db.sets.push(AlgSet {
+                    name: name.trim().to_string(),
+                    cases: vec![],
+                });
+                continue;
+            }
+            let (keyword, rest) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+            let rest = rest.trim();
+            let set = db
+                .sets
+                .last_mut()
+                .ok_or_else(|| syntax("expected a [set] first".to_string()))?;
+            if keyword == "case" {
+                set.cases.push(AlgCase {
+                    name: rest.to_string(),
+                    ..Default::default()
+                });
+                continue;
+            }
+            let case = set
+                .cases
+                .last_mut()
+                .ok_or_else(|| syntax(format!("expected a case before {keyword}")))?;
+            match keyword {
+                "alg" => {
+                    let moves = parse(rest).map_err(|err| syntax(err.to_string()))?.ops();
+                    case.algs.push(DbAlg {
+                        notation: rest.to_string(),
+                        moves,
+                    });
+                }
+                "tags" => case.tags.extend(
+                    rest.split(',')
+                        .map(str::trim)
+                        .filter(|tag| !tag.is_empty())
+                        .map(str::to_string),
+                ),
+                _ => return Err(syntax(format!("unknown keyword {keyword}"))),
+            }
+        }
+        Ok(db)
+    }
+
+    /// reads and verifies the database at `path`
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, Box<dyn Error>> {
+        let db = Self::parse(&std::fs::read_to_string(path)?)?;
+        match db.verify().into_iter().next() {
+            Some(err) => Err(err.into()),
+            None => Ok(db),
+        }
+    }
+
+    /// every algorithm not solving its case, empty when the database is sound
+    pub fn verify(&self) -> Vec<AlgDbError> {
+        let mut errors = vec![];
+        for set in &self.sets {
+            let key = set.key();
+            for case in &set.cases {
+                let mut expected = None;
+                for alg in &case.algs {
+                    let fields = || (set.name.clone(), case.name.clone(), alg.notation.clone());
+                    let Some(rubik) = alg.case() else {
+                        let (set, case, alg) = fields();
+                        errors.push(AlgDbError::BreaksF2l { set, case, alg });
+                        continue;
+                    };
+                    let solved = key(LlState::new(&rubik).expect("checked by DbAlg::case"));
+                    if *expected.get_or_insert(solved) != solved {
+                        let (set, case, alg) = fields();
+                        errors.push(AlgDbError::Mismatch { set, case, alg });
+                    } else if !set.names(&rubik, &case.name) {
+                        let (set, case, alg) = fields();
+                        errors.push(AlgDbError::Misnamed { set, case, alg });
+                    }
+                }
+            }
+        }
+        errors
+    }
+
+    pub fn set(&self, set: &str) -> Option<&AlgSet> {
+        self.sets.iter().find(|s| s.name == set)
+    }
+
+    pub fn case(&self, set: &str, case: &str) -> Option<&AlgCase> {
+        self.set(set)?.cases.iter().find(|c| c.name == case)
+    }
+
+    /// the cases of every set whose algorithms apply to `rubik`, which must have
+    /// the first two layers solved, the turn of U before them aside
+    pub fn by_effect(&self, rubik: &Rubik) -> Vec<(&AlgSet, &AlgCase)> {
+        let Some(state) = LlState::new(rubik) else {
+            return vec![];
+        };
+        self.sets
+            .iter()
+            .flat_map(|set| {
+                let key = set.key();
+                set.cases.iter().filter_map(move |case| {
+                    let alg = case.algs.first()?.case()?;
+                    let alg = LlState::new(&alg).expect("checked by DbAlg::case");
+                    (key(alg) == key(state)).then_some((set, case))
+                })
+            })
+            .collect()
+    }
+
+    /// the algorithm of the case with the fewest moves in `metric`, the first one on ties
+    pub fn best(&self, set: &str, case: &str, metric: Metric) -> Option<&DbAlg> {
+        self.case(set, case)?
+            .algs
+            .iter()
+            .min_by_key(|alg| metric.count(&alg.moves))
+    }
+}
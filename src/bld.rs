@@ -0,0 +1,352 @@
+//! Blindfolded memo: the corners and the edges of a scrambled cube are traced from a
+//! buffer piece, each sticker the buffer piece belongs to being memorized as a letter.
+//!
+//! Letters follow the Speffz scheme by default, the stickers of each face being lettered
+//! clockwise from the top left one, faces in the order U, L, F, R, B, D:
+//!
+//! ```text
+//!            A B              A
+//!            D C            D   B
+//!                             C
+//!   E F  I J  M N  Q R        E    I    M    Q
+//!   H G  L K  P O  T S      H   F L   J P   N T   R
+//!                             G    K    O    S
+//!            U V              U
+//!            X W            X   V
+//!                             W
+//! ```
+//!
+//! The cube is traced as its centers lie, so scrambles are expected in face turns.
+use std::{collections::HashMap, fmt::Display};
+
+use crate::{
+    cube::{clockwise, CubeFace},
+    CubePosition, Rubik,
+};
+
+/// A sticker: the slot of its piece and the face it shows on.
+pub type Sticker = (CubePosition, CubeFace);
+
+/// the 24 corner stickers in Speffz order, as their face then the other faces of their piece
+#[rustfmt::skip]
+const CORNER_STICKERS: [(CubeFace, [CubeFace; 2]); 24] = {
+    use CubeFace::*;
+    [
+        (U, [B, L]), (U, [B, R]), (U, [F, R]), (U, [F, L]),
+        (L, [U, B]), (L, [U, F]), (L, [D, F]), (L, [D, B]),
+        (F, [U, L]), (F, [U, R]), (F, [D, R]), (F, [D, L]),
+        (R, [U, F]), (R, [U, B]), (R, [D, B]), (R, [D, F]),
+        (B, [U, R]), (B, [U, L]), (B, [D, L]), (B, [D, R]),
+        (D, [F, L]), (D, [F, R]), (D, [B, R]), (D, [B, L]),
+    ]
+};
+
+/// the 24 edge stickers in Speffz order, as their face then the other face of their piece
+#[rustfmt::skip]
+const EDGE_STICKERS: [(CubeFace, CubeFace); 24] = {
+    use CubeFace::*;
+    [
+        (U, B), (U, R), (U, F), (U, L),
+        (L, U), (L, F), (L, D), (L, B),
+        (F, U), (F, R), (F, D), (F, L),
+        (R, U), (R, B), (R, D), (R, F),
+        (B, U), (B, L), (B, D), (B, R),
+        (D, F), (D, R), (D, B), (D, L),
+    ]
+};
+
+fn corner_sticker(index: usize) -> Sticker {
+    let (face, [a, b]) = CORNER_STICKERS[index];
+    let slot = CubePosition::from_faces([face, a, b]).expect("three adjacent faces");
+    (slot, face)
+}
+
+fn edge_sticker(index: usize) -> Sticker {
+    let (face, other) = EDGE_STICKERS[index];
+    let slot = CubePosition::from_faces([face, other]).expect("two adjacent faces");
+    (slot, face)
+}
+
+/// the letters of the corner and edge stickers, in Speffz sticker order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LetterScheme {
+    pub corners: [char; 24],
+    pub edges: [char; 24],
+}
+
+impl LetterScheme {
+    /// `corners` and `edges` letter the stickers Speffz letters `A` to `X` stand for
+    pub fn new(corners: [char; 24], edges: [char; 24]) -> Self {
+        Self { corners, edges }
+    }
+
+    pub fn speffz() -> Self {
+        let letters = std::array::from_fn(|index| (b'A' + index as u8) as char);
+        Self::new(letters, letters)
+    }
+
+    /// the letter of the sticker of the piece at `slot` showing on `face`
+    pub fn letter(&self, slot: CubePosition, face: CubeFace) -> Option<char> {
+        if slot.is_corner() {
+            (0..24)
+                .find(|index| corner_sticker(*index) == (slot, face))
+                .map(|index| self.corners[index])
+        } else if slot.is_edge() {
+            (0..24)
+                .find(|index| edge_sticker(*index) == (slot, face))
+                .map(|index| self.edges[index])
+        } else {
+            None
+        }
+    }
+}
+
+impl Default for LetterScheme {
+    fn default() -> Self {
+        Self::speffz()
+    }
+}
+
+/// The memo of one kind of pieces.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct PieceMemo {
+    /// the letters of the stickers the buffer piece goes to, in order
+    pub targets: Vec<char>,
+    /// the indices in `targets` of the cycle breaks, where a new cycle is started
+    pub breaks: Vec<usize>,
+    /// pieces left in their slot but twisted (1 clockwise, 2 counterclockwise)
+    /// or flipped (1), the buffer included when it ends twisted or flipped
+    pub in_place: Vec<(CubePosition, u8)>,
+}
+
+impl PieceMemo {
+    /// the targets grouped by two, the last pair being a single letter on parity
+    pub fn pairs(&self) -> Vec<String> {
+        self.targets
+            .chunks(2)
+            .map(|pair| pair.iter().collect())
+            .collect()
+    }
+}
+
+/// The memo of a scrambled cube.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Memo {
+    pub corners: PieceMemo,
+    pub edges: PieceMemo,
+    /// whether the corner and edge permutations are odd, which an odd number of corner
+    /// targets (and then of edge targets) tells
+    pub parity: bool,
+}
+
+fn slot_name(slot: CubePosition) -> String {
+    slot.faces().map(|face| format!("{face:?}")).collect()
+}
+
+impl Display for Memo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (name, memo) in [("edges", &self.edges), ("corners", &self.corners)] {
+            write!(f, "{name}: {}", memo.pairs().join(" "))?;
+            if !memo.breaks.is_empty() {
+                let breaks = memo
+                    .breaks
+                    .iter()
+                    .map(|index| memo.targets[*index].to_string())
+                    .collect::<Vec<_>>();
+                write!(f, " (cycle breaks: {})", breaks.join(", "))?;
+            }
+            writeln!(f)?;
+        }
+        if self.parity {
+            writeln!(f, "parity")?;
+        }
+        let twisted = self
+            .corners
+            .in_place
+            .iter()
+            .map(|(slot, twist)| {
+                let direction = if *twist == 1 { "cw" } else { "ccw" };
+                format!("{} {direction}", slot_name(*slot))
+            })
+            .collect::<Vec<_>>();
+        if !twisted.is_empty() {
+            writeln!(f, "twisted: {}", twisted.join(", "))?;
+        }
+        let flipped = self
+            .edges
+            .in_place
+            .iter()
+            .map(|(slot, _)| slot_name(*slot))
+            .collect::<Vec<_>>();
+        if !flipped.is_empty() {
+            writeln!(f, "flipped: {}", flipped.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+/// A memo generator, with its letter scheme and buffers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bld {
+    pub scheme: LetterScheme,
+    pub corner_buffer: (CubePosition, CubeFace),
+    pub edge_buffer: (CubePosition, CubeFace),
+}
+
+impl Default for Bld {
+    fn default() -> Self {
+        Self::new(LetterScheme::speffz())
+    }
+}
+
+impl Bld {
+    /// buffers UFR (its U sticker) and UF (its U sticker)
+    pub fn new(scheme: LetterScheme) -> Self {
+        Self {
+            scheme,
+            corner_buffer: (CubePosition::FUR, CubeFace::U),
+            edge_buffer: (CubePosition::FU, CubeFace::U),
+        }
+    }
+
+    /// panics if `slot` is not a corner lying on `face`
+    pub fn with_corner_buffer(mut self, slot: CubePosition, face: CubeFace) -> Self {
+        assert!(slot.is_corner() && slot.faces().any(|f| f == face));
+        self.corner_buffer = (slot, face);
+        self
+    }
+
+    /// panics if `slot` is not an edge lying on `face`
+    pub fn with_edge_buffer(mut self, slot: CubePosition, face: CubeFace) -> Self {
+        assert!(slot.is_edge() && slot.faces().any(|f| f == face));
+        self.edge_buffer = (slot, face);
+        self
+    }
+
+    pub fn memo(&self, rubik: &Rubik) -> Memo {
+        let rubik = rubik.normalized();
+        let corners = self.trace(&rubik, self.corner_buffer, corner_sticker, |sticker| {
+            self.scheme.letter(sticker.0, sticker.1)
+        });
+        let edges = self.trace(&rubik, self.edge_buffer, edge_sticker, |sticker| {
+            self.scheme.letter(sticker.0, sticker.1)
+        });
+        Memo {
+            parity: corners.targets.len() % 2 == 1,
+            corners,
+            edges,
+        }
+    }
+
+    /// shoot the buffer piece to its sticker until every piece is solved or in place,
+    /// `sticker` listing the stickers in scheme order
+    fn trace(
+        &self,
+        rubik: &Rubik,
+        buffer: Sticker,
+        sticker: fn(usize) -> Sticker,
+        letter: impl Fn(Sticker) -> Option<char>,
+    ) -> PieceMemo {
+        // the home sticker each sticker shows
+        let mut content = (0..24)
+            .map(sticker)
+            .map(|(slot, face)| {
+                let cube = &rubik.cubes[slot as usize];
+                ((slot, face), (cube.home(slot), cube.get(face)))
+            })
+            .collect::<HashMap<_, _>>();
+        // the slots in scheme order, where cycle breaks go first
+        let mut slots = vec![];
+        for slot in (0..24).map(|index| sticker(index).0) {
+            if !slots.contains(&slot) {
+                slots.push(slot);
+            }
+        }
+        let solved = |content: &HashMap<Sticker, Sticker>, slot: CubePosition| {
+            slot.faces()
+                .all(|face| content[&(slot, face)] == (slot, face))
+        };
+        let mut memo = PieceMemo::default();
+        let mut done = vec![buffer.0];
+        for slot in slots.iter().copied().filter(|slot| *slot != buffer.0) {
+            if content[&(slot, reference(slot))].0 == slot {
+                if !solved(&content, slot) {
+                    memo.in_place.push((slot, twist(&content, slot)));
+                }
+                done.push(slot);
+            }
+        }
+
+        loop {
+            let (home, face) = content[&buffer];
+            let target = if home != buffer.0 {
+                (home, face)
+            } else if let Some(slot) = slots
+                .iter()
+                .copied()
+                .find(|slot| !done.contains(slot) && !solved(&content, *slot))
+            {
+                memo.breaks.push(memo.targets.len());
+                (slot, reference(slot))
+            } else {
+                break;
+            };
+            memo.targets
+                .push(letter(target).expect("every sticker has a letter"));
+            for (from, to) in turning(buffer).into_iter().zip(turning(target)) {
+                let from = (buffer.0, from);
+                let to = (target.0, to);
+                let swapped = content[&from];
+                content.insert(from, content[&to]);
+                content.insert(to, swapped);
+            }
+            if solved(&content, target.0) {
+                done.push(target.0);
+            }
+        }
+        if !solved(&content, buffer.0) {
+            memo.in_place.push((buffer.0, twist(&content, buffer.0)));
+        }
+        memo
+    }
+}
+
+/// the face of the reference sticker of a slot: its U/D face, or for E slice edges its F/B face
+fn reference(slot: CubePosition) -> CubeFace {
+    slot.faces()
+        .find(|face| face.axis() == CubeFace::U.axis())
+        .or_else(|| slot.faces().find(|face| face.axis() == CubeFace::F.axis()))
+        .expect("a corner or an edge slot")
+}
+
+/// the faces of the slot of `sticker`, from its face and going clockwise around corners
+fn turning((slot, face): Sticker) -> Vec<CubeFace> {
+    let mut faces = vec![face];
+    while faces.len() < slot.faces().count() {
+        let last = faces[faces.len() - 1];
+        let next = slot
+            .faces()
+            .find(|next| {
+                !faces.contains(next) && (!slot.is_corner() || clockwise(slot, last, *next))
+            })
+            .expect("a next face");
+        faces.push(next);
+    }
+    faces
+}
+
+/// the twist or flip of the piece in its home slot, as [`crate::cube::Cube::orientation`]
+fn twist(content: &HashMap<Sticker, Sticker>, slot: CubePosition) -> u8 {
+    let ud = reference(slot);
+    let face = slot
+        .faces()
+        .find(|face| content[&(slot, *face)].1 == ud)
+        .expect("the reference sticker shows somewhere on its slot");
+    if face == ud {
+        0
+    } else if !slot.is_corner() || clockwise(slot, ud, face) {
+        1
+    } else {
+        2
+    }
+}
@@ -1,5 +1,8 @@
 use crate::cube::CubeFace;
 
+/// An sRGB color.
+pub type Rgb = [u8; 3];
+
 #[repr(u8)]
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub enum RubikColor {
@@ -12,6 +15,46 @@ pub enum RubikColor {
 }
 
 impl RubikColor {
+    pub const ALL: [Self; 6] = [
+        RubikColor::White,
+        RubikColor::Yellow,
+        RubikColor::Red,
+        RubikColor::Orange,
+        RubikColor::Blue,
+        RubikColor::Green,
+    ];
+
+    /// the initial of the color, as `W` for white
+    pub const fn letter(self) -> char {
+        match self {
+            RubikColor::White => 'W',
+            RubikColor::Yellow => 'Y',
+            RubikColor::Red => 'R',
+            RubikColor::Orange => 'O',
+            RubikColor::Blue => 'B',
+            RubikColor::Green => 'G',
+        }
+    }
+
+    /// a typical sRGB value of the color on a cube
+    pub const fn rgb(self) -> Rgb {
+        match self {
+            RubikColor::White => [255, 255, 255],
+            RubikColor::Yellow => [255, 213, 0],
+            RubikColor::Red => [196, 30, 58],
+            RubikColor::Orange => [255, 88, 0],
+            RubikColor::Blue => [0, 81, 186],
+            RubikColor::Green => [0, 158, 96],
+        }
+    }
+
+    /// the color of initial `letter`, in either case
+    pub fn from_letter(letter: char) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|color| color.letter() == letter.to_ascii_uppercase())
+    }
+
     pub const fn classic_map() -> CubeFaceMap<Self> {
         CubeFaceMap::new(
             RubikColor::Red,
@@ -48,6 +91,17 @@ impl<T> CubeFaceMap<T> {
             CubeFace::D => &self.d,
         }
     }
+    /// the map of `f` applied to the value of each face
+    pub fn map<U>(&self, f: impl Fn(&T) -> U) -> CubeFaceMap<U> {
+        CubeFaceMap::new(
+            f(&self.f),
+            f(&self.r),
+            f(&self.u),
+            f(&self.b),
+            f(&self.l),
+            f(&self.d),
+        )
+    }
 }
 
 impl<T> std::ops::Index<CubeFace> for CubeFaceMap<T> {
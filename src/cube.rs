@@ -1,4 +1,4 @@
-use crate::permutation::CubePermutation;
+use crate::{permutation::CubePermutation, CubePosition};
 use std::{collections::HashMap, fmt::Debug, ops::AddAssign};
 
 #[derive(Clone, Copy, PartialEq, Eq, Hash)]
@@ -8,7 +8,7 @@ pub struct Cube {
 }
 
 #[repr(u8)]
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum CubeFace {
     F = unsafe { Self::align(CubePermutation::UNIT.0) },
     B = unsafe { Self::align(CubePermutation::Y_2.0) },
@@ -19,6 +19,43 @@ pub enum CubeFace {
 }
 
 impl CubeFace {
+    pub const ALL: [CubeFace; 6] = [
+        CubeFace::U,
+        CubeFace::D,
+        CubeFace::F,
+        CubeFace::B,
+        CubeFace::L,
+        CubeFace::R,
+    ];
+    /// outward normal of the face, x from L to R, y from D to U and z from B to F
+    pub const fn vector(self) -> [i8; 3] {
+        match self {
+            CubeFace::F => [0, 0, 1],
+            CubeFace::B => [0, 0, -1],
+            CubeFace::R => [1, 0, 0],
+            CubeFace::L => [-1, 0, 0],
+            CubeFace::U => [0, 1, 0],
+            CubeFace::D => [0, -1, 0],
+        }
+    }
+    /// 0 for F/B, 1 for L/R, 2 for U/D, as [`crate::RubikLayer::axis`]
+    pub const fn axis(self) -> u8 {
+        match self {
+            CubeFace::F | CubeFace::B => 0,
+            CubeFace::L | CubeFace::R => 1,
+            CubeFace::U | CubeFace::D => 2,
+        }
+    }
+    pub const fn opposite(self) -> Self {
+        match self {
+            CubeFace::F => CubeFace::B,
+            CubeFace::B => CubeFace::F,
+            CubeFace::R => CubeFace::L,
+            CubeFace::L => CubeFace::R,
+            CubeFace::U => CubeFace::D,
+            CubeFace::D => CubeFace::U,
+        }
+    }
     const unsafe fn align(value: u8) -> u8 {
         let mut value = value;
         if value & 0b11 != 0b00 {
@@ -62,6 +99,80 @@ impl Cube {
         CubeFace::from_cube_permutation(self.rotation.compose(face.as_cube_permutation()))
     }
 
+    /// the home position of the cube when it sits at `at`.
+    ///
+    /// every move turns the cubes it moves, so the rotation of a cube tells where it comes from
+    pub fn home(&self, at: CubePosition) -> CubePosition {
+        CubePosition::from_faces(at.faces().map(|face| self.get(face)))
+            .expect("a rotation never maps faces to opposite faces")
+    }
+
+    /// orientation of the cube when it sits at `at`:
+    /// - for corners, how many clockwise twists bring its U/D sticker onto the U/D face (0..3),
+    /// - for edges, whether it is flipped relative to `<U, D, L, R, F2, B2>` (0..2),
+    ///   see [`Cube::edge_orientation`],
+    /// - always 0 for centers and the core.
+    pub fn orientation(&self, at: CubePosition) -> u8 {
+        let faces = at.faces().collect::<Vec<_>>();
+        match faces.len() {
+            3 => {
+                let Some(sticker) = faces
+                    .iter()
+                    .copied()
+                    .find(|face| self.get(*face).axis() == CubeFace::U.axis())
+                else {
+                    unreachable!("every corner has a U/D sticker")
+                };
+                let ud = faces
+                    .iter()
+                    .copied()
+                    .find(|face| face.axis() == CubeFace::U.axis())
+                    .expect("every corner slot lies on U or D");
+                if sticker == ud {
+                    0
+                } else if clockwise(at, ud, sticker) {
+                    1
+                } else {
+                    2
+                }
+            }
+            2 => self.edge_orientation(at, CubeFace::F),
+            _ => 0,
+        }
+    }
+
+    /// whether the edge sitting at `at` is flipped (1) or not (0) relative to the axis of `axis`,
+    /// that is relative to the moves turning the layers of `axis` only by half turns.
+    ///
+    /// An edge is good when its reference sticker, the U/D one if it has one and the `axis`
+    /// one otherwise, lies on the reference face of its slot, chosen the same way
+    /// (F/B replaces U/D for the U/D axis).
+    pub fn edge_orientation(&self, at: CubePosition, axis: CubeFace) -> u8 {
+        let faces = at.faces().collect::<Vec<_>>();
+        debug_assert_eq!(faces.len(), 2, "{at:?} is not an edge slot");
+        let first = if axis.axis() == CubeFace::U.axis() {
+            CubeFace::F.axis()
+        } else {
+            CubeFace::U.axis()
+        };
+        let home = faces.iter().map(|face| self.get(*face)).collect::<Vec<_>>();
+        let reference = |faces: &[CubeFace]| {
+            faces
+                .iter()
+                .copied()
+                .find(|face| face.axis() == first)
+                .or_else(|| faces.iter().copied().find(|face| face.axis() == axis.axis()))
+                .expect("an edge lies on two different axes")
+        };
+        let sticker = reference(&home);
+        let direction = faces
+            .iter()
+            .copied()
+            .find(|face| self.get(*face) == sticker)
+            .expect("the sticker shows on one of the faces");
+        (direction.axis() != reference(&faces).axis()) as u8
+    }
+
     pub fn entropy(iter: impl Iterator<Item = Self>) -> f64 {
         let (map, n) = iter.fold((HashMap::new(), 0usize), |(mut map, count), cube| {
             map.entry(cube).or_insert(0_usize).add_assign(1);
@@ -72,6 +183,18 @@ impl Cube {
     }
 }
 
+/// whether `to` follows `from` going clockwise around the corner at `at`, looking at the corner from outside
+pub(crate) fn clockwise(at: CubePosition, from: CubeFace, to: CubeFace) -> bool {
+    let third = at
+        .faces()
+        .find(|face| *face != from && *face != to)
+        .expect("a corner lies on three faces");
+    let [a, b, c] = [from.vector(), to.vector(), third.vector()];
+    let det = a[0] * (b[1] * c[2] - b[2] * c[1]) - a[1] * (b[0] * c[2] - b[2] * c[0])
+        + a[2] * (b[0] * c[1] - b[1] * c[0]);
+    det < 0
+}
+
 impl Debug for Cube {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("Cube")
@@ -0,0 +1,399 @@
+//! Facelets: the 54 stickers of the cube, addressed by face, row and column.
+//!
+//! Each face is read row by row from the top left sticker, as seen from outside on the
+//! usual unfolded net:
+//!
+//! ```text
+//!          U
+//!       L  F  R  B
+//!          D
+//! ```
+//!
+//! that is F, R, B and L with U on top, U with B on top and D with F on top. Facelets
+//! are indexed `0..54` face by face in the order U, R, F, D, L, B, as in the common
+//! 54 letter facelet strings.
+//!
+//! A state can be entered from the colors of its facelets, see
+//! [`Rubik::from_face_colors`].
+use std::fmt::Display;
+
+use crate::{
+    colored::{CubeFaceMap, RubikColor},
+    cube::{Cube, CubeFace},
+    permutation::CubePermutation,
+    CubePosition, Rubik,
+};
+
+/// A sticker position, `row` and `col` in `0..3`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Facelet {
+    pub face: CubeFace,
+    pub row: u8,
+    pub col: u8,
+}
+
+impl Facelet {
+    /// the faces in facelet index order
+    pub const FACES: [CubeFace; 6] = [
+        CubeFace::U,
+        CubeFace::R,
+        CubeFace::F,
+        CubeFace::D,
+        CubeFace::L,
+        CubeFace::B,
+    ];
+
+    /// `None` if `row` or `col` is out of `0..3`
+    pub fn new(face: CubeFace, row: u8, col: u8) -> Option<Self> {
+        (row < 3 && col < 3).then_some(Self { face, row, col })
+    }
+
+    /// the facelet of index `index`, `None` past 53
+    pub fn from_index(index: usize) -> Option<Self> {
+        let face = *Self::FACES.get(index / 9)?;
+        Self::new(face, (index % 9 / 3) as u8, (index % 3) as u8)
+    }
+
+    pub fn index(self) -> usize {
+        let face = Self::FACES
+            .iter()
+            .position(|face| *face == self.face)
+            .expect("every face has an index");
+        face * 9 + self.row as usize * 3 + self.col as usize
+    }
+
+    /// the 54 facelets in index order
+    pub fn all() -> impl Iterator<Item = Self> {
+        (0..54).filter_map(Self::from_index)
+    }
+
+    /// the 9 facelets of `face` in reading order
+    pub fn of_face(face: CubeFace) -> impl Iterator<Item = Self> {
+        (0..9).filter_map(move |index| Self::new(face, index / 3, index % 3))
+    }
+
+    /// the slot of the piece showing the facelet, and the face it shows on
+    pub fn position(self) -> (CubePosition, CubeFace) {
+        let (right, down) = axes(self.face);
+        let normal = self.face.vector();
+        let coordinate = std::array::from_fn(|i| {
+            normal[i] + (self.col as i8 - 1) * right[i] + (self.row as i8 - 1) * down[i]
+        });
+        let slot = CubePosition::from_coordinate(coordinate).expect("a slot of the cube");
+        (slot, self.face)
+    }
+
+    /// the facelet `slot` shows on `face`, `None` if `slot` does not lie on `face`
+    pub fn from_position(slot: CubePosition, face: CubeFace) -> Option<Self> {
+        if !slot.faces().any(|f| f == face) {
+            return None;
+        }
+        let (right, down) = axes(face);
+        let coordinate = slot.coordinate();
+        let dot = |axis: [i8; 3]| (0..3).map(|i| axis[i] * coordinate[i]).sum::<i8>();
+        Self::new(face, (dot(down) + 1) as u8, (dot(right) + 1) as u8)
+    }
+}
+
+impl Display for Facelet {
+    /// the face then the index in the face, as `U5` for the U center
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}{}", self.face, self.row * 3 + self.col + 1)
+    }
+}
+
+/// the directions of the columns (left to right) and the rows (top to bottom) of `face`
+const fn axes(face: CubeFace) -> ([i8; 3], [i8; 3]) {
+    match face {
+        CubeFace::U => ([1, 0, 0], [0, 0, 1]),
+        CubeFace::D => ([1, 0, 0], [0, 0, -1]),
+        CubeFace::F => ([1, 0, 0], [0, -1, 0]),
+        CubeFace::B => ([-1, 0, 0], [0, -1, 0]),
+        CubeFace::L => ([0, 0, 1], [0, -1, 0]),
+        CubeFace::R => ([0, 0, -1], [0, -1, 0]),
+    }
+}
+
+impl Rubik {
+    /// the home face of the sticker shown at `facelet`, that is its color
+    pub fn facelet(&self, facelet: Facelet) -> CubeFace {
+        let (slot, face) = facelet.position();
+        self.cubes[slot as usize].get(face)
+    }
+
+    /// the home face of the sticker shown on `face` at `row` and `col`, panics if `row`
+    /// or `col` is out of `0..3`
+    pub fn sticker(&self, face: CubeFace, row: u8, col: u8) -> CubeFace {
+        self.facelet(Facelet::new(face, row, col).expect("row and col in 0..3"))
+    }
+
+    /// the home faces of the 9 stickers shown on `face`, in reading order
+    pub fn face_stickers(&self, face: CubeFace) -> impl Iterator<Item = CubeFace> + '_ {
+        Facelet::of_face(face).map(|facelet| self.facelet(facelet))
+    }
+}
+
+/// A problem in a cube state given as the colors of its stickers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ColorInputError {
+    /// a face does not have 9 stickers, whitespace aside
+    FaceLength { face: CubeFace, found: usize },
+    /// two centers have the same color
+    DuplicateCenter { facelet: Facelet, color: char },
+    /// a sticker of a color that is none of the six
+    UnknownColor { facelet: Facelet, color: char },
+    /// a color does not show on 9 stickers
+    ColorCount { color: char, found: usize },
+    /// the centers do not show the colors of the scheme in a way the cube can be held
+    ImpossibleCenters { facelet: Facelet },
+    /// the colors of the stickers of a piece are not those of any piece
+    ImpossiblePiece {
+        facelets: Vec<Facelet>,
+        colors: String,
+    },
+    /// the colors of the stickers of a piece are those of a piece seen elsewhere
+    DuplicatePiece {
+        facelets: Vec<Facelet>,
+        colors: String,
+    },
+    /// the pieces are those of a cube but no face turns solve them, `reason` telling which
+    /// invariant breaks and `pieces` giving the facelets of the twisted corners, the
+    /// flipped edges or the pieces out of their home slot, the centers taken home: on a
+    /// scrambled cube, any of them may be the one misread
+    Unsolvable {
+        reason: &'static str,
+        pieces: Vec<Vec<Facelet>>,
+    },
+}
+
+impl Display for ColorInputError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let names = |facelets: &[Facelet]| {
+            facelets
+                .iter()
+                .map(|facelet| facelet.to_string())
+                .collect::<Vec<_>>()
+                .join(" ")
+        };
+        match self {
+            ColorInputError::FaceLength { face, found } => {
+                write!(f, "face {face:?} has {found} stickers, expected 9")
+            }
+            ColorInputError::DuplicateCenter { facelet, color } => {
+                write!(f, "{facelet}: another center is already {color}")
+            }
+            ColorInputError::UnknownColor { facelet, color } => {
+                write!(f, "{facelet}: unknown color {color}")
+            }
+            ColorInputError::ColorCount { color, found } => {
+                write!(f, "color {color} shows {found} times, expected 9")
+            }
+            ColorInputError::ImpossibleCenters { facelet } => {
+                write!(f, "{facelet}: the centers do not match the color scheme")
+            }
+            ColorInputError::ImpossiblePiece { facelets, colors } => {
+                write!(f, "{}: no piece has the colors {colors}", names(facelets))
+            }
+            ColorInputError::DuplicatePiece { facelets, colors } => {
+                write!(f, "{}: the piece {colors} shows twice", names(facelets))
+            }
+            ColorInputError::Unsolvable { reason, pieces } => {
+                let pieces = pieces
+                    .iter()
+                    .map(|facelets| names(facelets))
+                    .collect::<Vec<_>>();
+                write!(f, "{}: unsolvable, {reason}", pieces.join(", "))
+            }
+        }
+    }
+}
+
+impl std::error::Error for ColorInputError {}
+
+impl Rubik {
+    /// the state showing the colors of `faces`, each face read as 9 color letters in the
+    /// order of [`Facelet::of_face`], whitespace aside.
+    ///
+    /// Letters are any characters, the center of each face giving the color of its face.
+    pub fn from_face_colors(faces: &CubeFaceMap<&str>) -> Result<Self, ColorInputError> {
+        let stickers = read_faces(faces)?;
+        let mut letters = vec![];
+        for face in Facelet::FACES {
+            let facelet = Facelet::new(face, 1, 1).expect("the center");
+            let color = stickers[facelet.index()];
+            if letters.iter().any(|(letter, _)| *letter == color) {
+                return Err(ColorInputError::DuplicateCenter { facelet, color });
+            }
+            letters.push((color, face));
+        }
+        from_stickers(&stickers, &letters)
+    }
+
+    /// the state showing the colors of `faces`, read as with [`Rubik::from_face_colors`]
+    /// but with the initials of [`RubikColor`] in either case, `scheme` giving the color of
+    /// each face. The cube may be held in any orientation.
+    pub fn from_face_colors_with_scheme(
+        faces: &CubeFaceMap<&str>,
+        scheme: &CubeFaceMap<RubikColor>,
+    ) -> Result<Self, ColorInputError> {
+        let stickers = read_faces(faces)?.map(|letter| letter.to_ascii_uppercase());
+        let letters = CubeFace::ALL.map(|face| (scheme[face].letter(), face));
+        from_stickers(&stickers, &letters)
+    }
+}
+
+/// the letters of the 54 facelets, in index order
+pub(crate) fn read_faces(faces: &CubeFaceMap<&str>) -> Result<[char; 54], ColorInputError> {
+    let mut stickers = ['\0'; 54];
+    for (index, face) in Facelet::FACES.into_iter().enumerate() {
+        let letters = faces[face]
+            .chars()
+            .filter(|letter| !letter.is_whitespace())
+            .collect::<Vec<_>>();
+        if letters.len() != 9 {
+            return Err(ColorInputError::FaceLength {
+                face,
+                found: letters.len(),
+            });
+        }
+        stickers[index * 9..index * 9 + 9].copy_from_slice(&letters);
+    }
+    Ok(stickers)
+}
+
+/// the state showing `stickers`, `letters` giving the home face of each color
+fn from_stickers(
+    stickers: &[char; 54],
+    letters: &[(char, CubeFace)],
+) -> Result<Rubik, ColorInputError> {
+    let mut homes = [CubeFace::U; 54];
+    for facelet in Facelet::all() {
+        let color = stickers[facelet.index()];
+        homes[facelet.index()] = letters
+            .iter()
+            .find(|(letter, _)| *letter == color)
+            .map(|(_, face)| *face)
+            .ok_or(ColorInputError::UnknownColor { facelet, color })?;
+    }
+    for (color, face) in letters {
+        let found = homes.iter().filter(|home| *home == face).count();
+        if found != 9 {
+            return Err(ColorInputError::ColorCount {
+                color: *color,
+                found,
+            });
+        }
+    }
+
+    // the rotation of a piece at `slot` showing the colors of its facelets on `faces`,
+    // the centers being read together as the core
+    let home_of = |slot: CubePosition, face: CubeFace| {
+        let slot = if slot == CubePosition::C {
+            CubePosition::from_faces([face]).expect("the center of a face")
+        } else {
+            slot
+        };
+        let facelet = Facelet::from_position(slot, face).expect("a face of the slot");
+        homes[facelet.index()]
+    };
+    let rotation_at = |slot: CubePosition, faces: &[CubeFace]| {
+        CubePermutation::enumerate()
+            .map(Cube::new_with_rotation)
+            .find(|cube| {
+                faces
+                    .iter()
+                    .all(|face| cube.get(*face) == home_of(slot, *face))
+            })
+    };
+    let Some(core) = rotation_at(CubePosition::C, &CubeFace::ALL) else {
+        let face = Facelet::FACES
+            .into_iter()
+            .find(|face| home_of(CubePosition::C, *face) != *face)
+            .unwrap_or(CubeFace::U);
+        let facelet = Facelet::new(face, 1, 1).expect("the center");
+        return Err(ColorInputError::ImpossibleCenters { facelet });
+    };
+    let mut rubik = Rubik::new();
+    for slot in CubePosition::all().filter(|slot| slot.is_center() || *slot == CubePosition::C) {
+        rubik.cubes[slot as usize] = core;
+    }
+    let mut seen = vec![];
+    for slot in CubePosition::all().filter(|slot| slot.is_corner() || slot.is_edge()) {
+        let faces = slot.faces().collect::<Vec<_>>();
+        let facelets = faces
+            .iter()
+            .map(|face| Facelet::from_position(slot, *face).expect("a face of the slot"))
+            .collect::<Vec<_>>();
+        let colors = facelets
+            .iter()
+            .map(|facelet| stickers[facelet.index()])
+            .collect::<String>();
+        let Some(cube) = rotation_at(slot, &faces) else {
+            return Err(ColorInputError::ImpossiblePiece { facelets, colors });
+        };
+        let home = cube.home(slot);
+        if seen.contains(&home) {
+            return Err(ColorInputError::DuplicatePiece { facelets, colors });
+        }
+        seen.push(home);
+        rubik.cubes[slot as usize] = cube;
+    }
+    check_solvable(&rubik, core)?;
+    Ok(rubik)
+}
+
+/// whether face turns solve `rubik`, held with its core rotated as `core`: once held with
+/// its centers home, the corner twists sum to 0 modulo 3, the edge flips to 0 modulo 2
+/// and the corner and edge permutations are of the same parity
+fn check_solvable(rubik: &Rubik, core: Cube) -> Result<(), ColorInputError> {
+    let turn_back = Rubik { cubes: [core; 27] }.inverse();
+    let held = rubik.compose(&turn_back);
+    // the facelets showing the piece at `slot` once held, as `rubik` shows them
+    let facelets = |slot: CubePosition| {
+        let shown = turn_back.cubes[slot as usize].home(slot);
+        shown
+            .faces()
+            .map(|face| Facelet::from_position(shown, face).expect("a face of the slot"))
+            .collect::<Vec<_>>()
+    };
+    let unsolvable = |reason, slots: Vec<CubePosition>| ColorInputError::Unsolvable {
+        reason,
+        pieces: slots.into_iter().map(facelets).collect(),
+    };
+    let slots =
+        |kind: fn(CubePosition) -> bool| CubePosition::all().filter(move |slot| kind(*slot));
+    let orientation = |slot: CubePosition| held.cubes[slot as usize].orientation(slot);
+    for (kind, modulo, reason) in [
+        (
+            CubePosition::is_corner as fn(_) -> _,
+            3,
+            "the corner twists do not add up",
+        ),
+        (CubePosition::is_edge, 2, "the edge flips do not add up"),
+    ] {
+        if slots(kind).map(orientation).sum::<u8>() % modulo != 0 {
+            let turned = slots(kind).filter(|slot| orientation(*slot) != 0);
+            return Err(unsolvable(reason, turned.collect()));
+        }
+    }
+    // the parity of a permutation is that of its number of inversions
+    let parity = |kind: fn(CubePosition) -> bool| {
+        let homes = slots(kind)
+            .map(|slot| held.cubes[slot as usize].home(slot) as usize)
+            .collect::<Vec<_>>();
+        let inversions = (0..homes.len())
+            .flat_map(|i| (i + 1..homes.len()).map(move |j| (i, j)))
+            .filter(|&(i, j)| homes[i] > homes[j])
+            .count();
+        inversions % 2
+    };
+    if parity(CubePosition::is_corner) != parity(CubePosition::is_edge) {
+        let away = slots(|slot| slot.is_corner() || slot.is_edge())
+            .filter(|slot| held.cubes[*slot as usize].home(*slot) != *slot);
+        return Err(unsolvable(
+            "the corner and edge permutations differ in parity",
+            away.collect(),
+        ));
+    }
+    Ok(())
+}
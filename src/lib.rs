@@ -6,12 +6,21 @@ use cube::{Cube, CubeFace};
 use solver::RubikSolver;
 use transform::RubikLayerTransform;
 
+#[cfg(feature = "singmaster")]
+pub mod algdb;
+pub mod bld;
 pub mod colored;
 pub mod cube;
+pub mod facelet;
 pub mod parser;
+pub mod partial;
 pub mod permutation;
+pub mod piece;
 pub mod prelude;
+pub mod render;
+pub mod scan;
 pub mod solver;
+pub mod tables;
 pub mod transform;
 /*
             UU_UU_UU
@@ -119,6 +128,24 @@ impl RubikLayer {
     pub const fn indexes(&self) -> &[u8; 9] {
         &self.cude_indexes
     }
+    /// the axis the layer turns around: 0 for F/S/B, 1 for L/M/R, 2 for U/E/D.
+    ///
+    /// layers sharing an axis are parallel, their moves commute
+    pub const fn axis(&self) -> u8 {
+        match self.marker().as_bytes()[0] {
+            b'F' | b'S' | b'B' => 0,
+            b'L' | b'M' | b'R' => 1,
+            _ => 2,
+        }
+    }
+    /// position of the layer along its axis: 0 for F/L/U, 1 for S/M/E, 2 for B/R/D
+    pub const fn depth(&self) -> u8 {
+        match self.marker().as_bytes()[0] {
+            b'F' | b'L' | b'U' => 0,
+            b'S' | b'M' | b'E' => 1,
+            _ => 2,
+        }
+    }
     pub const F: Self = Self {
         cude_indexes: [00, 01, 02, 03, 04, 05, 06, 07, 08],
     };
@@ -255,6 +282,48 @@ impl Rubik {
     pub fn entropy(&self) -> f64 {
         Cube::entropy(self.active_cubes().copied())
     }
+
+    /// the edge slots holding an edge flipped relative to the axis of `axis`,
+    /// see [`Cube::edge_orientation`]
+    pub fn bad_edges(&self, axis: CubeFace) -> impl Iterator<Item = CubePosition> + '_ {
+        CubePosition::all()
+            .filter(|slot| slot.is_edge())
+            .filter(move |slot| self.cubes[*slot as usize].edge_orientation(*slot, axis) != 0)
+    }
+
+    pub fn bad_edge_count(&self, axis: CubeFace) -> usize {
+        self.bad_edges(axis).count()
+    }
+
+    /// the state reached by playing the moves leading to `self` then those leading to `next`,
+    /// both from the solved cube
+    pub fn compose(&self, next: &Rubik) -> Rubik {
+        let mut cubes = [Cube::new(); 27];
+        for (slot, cube) in CubePosition::all().zip(next.cubes.iter()) {
+            let from = self.cubes[cube.home(slot) as usize];
+            cubes[slot as usize] = Cube::new_with_rotation(from.rotation.compose(cube.rotation));
+        }
+        Rubik { cubes }
+    }
+
+    /// the state undoing `self`, so that composing both gives the solved cube
+    pub fn inverse(&self) -> Rubik {
+        let mut cubes = [Cube::new(); 27];
+        for (slot, cube) in CubePosition::all().zip(self.cubes.iter()) {
+            cubes[cube.home(slot) as usize] = Cube::new_with_rotation(cube.rotation.inverse());
+        }
+        Rubik { cubes }
+    }
+
+    /// the state with the centers and the core back to their solved rotation, as face turns
+    /// only spin them
+    pub fn normalized(&self) -> Rubik {
+        let mut rubik = self.clone();
+        CubePosition::all()
+            .filter(|slot| slot.is_center() || *slot == CubePosition::C)
+            .for_each(|slot| rubik.cubes[slot as usize] = Cube::new());
+        rubik
+    }
 }
 
 impl Default for Rubik {
@@ -325,6 +394,58 @@ impl CubePosition {
             None
         }
     }
+    pub fn all() -> impl Iterator<Item = Self> {
+        (0..27).map(|code| unsafe { Self::from_u8(code) })
+    }
+    /// coordinates of the slot, x from L to R, y from D to U and z from B to F, each in `-1..=1`
+    pub const fn coordinate(self) -> [i8; 3] {
+        let code = self as u8;
+        [
+            (code % 3) as i8 - 1,
+            1 - ((code % 9) / 3) as i8,
+            1 - (code / 9) as i8,
+        ]
+    }
+    pub fn from_coordinate([x, y, z]: [i8; 3]) -> Option<Self> {
+        if [x, y, z].iter().all(|c| (-1..=1).contains(c)) {
+            Self::try_from_u8(((x + 1) + (1 - y) * 3 + (1 - z) * 9) as u8)
+        } else {
+            None
+        }
+    }
+    /// the outer faces the slot lies on: none for the core, one for centers, two for edges and three for corners
+    pub fn faces(self) -> impl Iterator<Item = CubeFace> {
+        let coordinate = self.coordinate();
+        CubeFace::ALL.into_iter().filter(move |face| {
+            let vector = face.vector();
+            (0..3).any(|i| vector[i] != 0 && vector[i] == coordinate[i])
+        })
+    }
+    /// the slot lying on all of `faces`, `None` if two of them are opposite
+    pub fn from_faces(faces: impl IntoIterator<Item = CubeFace>) -> Option<Self> {
+        let mut coordinate = [0; 3];
+        for face in faces {
+            let vector = face.vector();
+            for i in 0..3 {
+                if vector[i] != 0 {
+                    if coordinate[i] == -vector[i] {
+                        return None;
+                    }
+                    coordinate[i] = vector[i];
+                }
+            }
+        }
+        Self::from_coordinate(coordinate)
+    }
+    pub fn is_corner(self) -> bool {
+        self.faces().count() == 3
+    }
+    pub fn is_edge(self) -> bool {
+        self.faces().count() == 2
+    }
+    pub fn is_center(self) -> bool {
+        self.faces().count() == 1
+    }
     pub fn horizon_layer(self) -> &'static RubikLayer {
         match (self as u8) % 9 {
             0..=2 => &RubikLayer::U,
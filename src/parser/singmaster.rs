@@ -22,6 +22,28 @@ pub fn parse<'a>(src: &'a str) -> Result<RubikTransform, Box<dyn Error + 'a>> {
     }
 }
 
+/// parses a line of a fewest moves session, where a parenthesized group without modifiers,
+/// like `(R U)`, holds moves of the inverse scramble, as in `F (R U) D'`. Groups followed by
+/// a modifier, like `(R U)2` or `(R U)'`, are plain groupings of the normal side.
+///
+/// Returns the moves of the normal side and those of the inverse side, each in order.
+pub fn parse_niss<'a>(
+    src: &'a str,
+) -> Result<(RubikTransform, RubikTransform), Box<dyn Error + 'a>> {
+    let input = src.as_ref();
+    let (rest, output) = many0(modified_move)(input)?;
+    if !rest.is_empty() {
+        return Err(format!("parse error near: {}", String::from_utf8(rest.to_vec())?).into());
+    }
+    let (inverse, normal): (Vec<_>, Vec<_>) = output
+        .into_iter()
+        .partition(|m| matches!(m.rubik_move, RubikMove::Group(_)) && m.modifiers.is_empty());
+    Ok((
+        RubikTransform::from(normal.as_slice()),
+        RubikTransform::from(inverse.as_slice()),
+    ))
+}
+
 #[derive(Debug, Clone, Copy)]
 enum Modifier {
     Inverse,
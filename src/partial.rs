@@ -0,0 +1,282 @@
+//! Partial states: cubes of which only some stickers are known, completed into the
+//! solvable states showing them.
+//!
+//! The cube may be held in any orientation the known centers show, the centers being
+//! taken home when they may be, as with unknown centers. A completion places every corner and
+//! edge so that the known stickers show, with the corner twists summing to 0 modulo 3,
+//! the edge flips summing to 0 modulo 2 and the corner and edge permutations of the
+//! same parity, as for every state reachable by face turns.
+use std::collections::HashMap;
+
+use crate::{
+    colored::{CubeFaceMap, RubikColor},
+    cube::{Cube, CubeFace},
+    facelet::{read_faces, ColorInputError, Facelet},
+    permutation::CubePermutation,
+    CubePosition, Rubik,
+};
+
+/// The home face, that is the color, of each facelet when known, in [`Facelet`]
+/// index order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartialState {
+    pub facelets: [Option<CubeFace>; 54],
+}
+
+/// What a partial state can be.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Completion {
+    /// no solvable state shows the known stickers
+    Impossible,
+    Unique(Rubik),
+    /// the number of solvable states showing the known stickers, at least 2
+    Many(u128),
+}
+
+impl PartialState {
+    pub fn new(facelets: [Option<CubeFace>; 54]) -> Self {
+        Self { facelets }
+    }
+
+    /// the stickers of `rubik` for which `known` holds
+    pub fn masked(rubik: &Rubik, known: impl Fn(Facelet) -> bool) -> Self {
+        Self::new(std::array::from_fn(|index| {
+            let facelet = Facelet::from_index(index).expect("a facelet");
+            known(facelet).then(|| rubik.facelet(facelet))
+        }))
+    }
+
+    /// the stickers of `faces`, read as with [`Rubik::from_face_colors_with_scheme`],
+    /// `?` standing for an unknown sticker
+    pub fn from_face_colors_with_scheme(
+        faces: &CubeFaceMap<&str>,
+        scheme: &CubeFaceMap<RubikColor>,
+    ) -> Result<Self, ColorInputError> {
+        let stickers = read_faces(faces)?;
+        let mut facelets = [None; 54];
+        for facelet in Facelet::all() {
+            let color = stickers[facelet.index()];
+            if color == '?' {
+                continue;
+            }
+            let face = RubikColor::from_letter(color)
+                .and_then(|color| {
+                    CubeFace::ALL
+                        .into_iter()
+                        .find(|face| scheme[*face] == color)
+                })
+                .ok_or(ColorInputError::UnknownColor { facelet, color })?;
+            facelets[facelet.index()] = Some(face);
+        }
+        Ok(Self::new(facelets))
+    }
+
+    /// the number of solvable states showing the known stickers
+    pub fn count(&self) -> u128 {
+        let Some(core) = self.core() else {
+            return 0;
+        };
+        let [corners, edges] = self.held(core).pieces();
+        let (corners, edges) = (corners.count(), edges.count());
+        (0..2).map(|parity| corners[parity] * edges[parity]).sum()
+    }
+
+    /// at most `limit` of the solvable states showing the known stickers
+    pub fn completions(&self, limit: usize) -> Vec<Rubik> {
+        let Some(core) = self.core() else {
+            return vec![];
+        };
+        let [corners, edges] = self.held(core).pieces();
+        let held_as_shown = Rubik { cubes: [core; 27] };
+        let mut completions = vec![];
+        for parity in 0..2 {
+            let corner_sets = corners.walk(parity, limit);
+            let edge_sets = edges.walk(parity, limit);
+            for corner_set in &corner_sets {
+                for edge_set in &edge_sets {
+                    if completions.len() == limit {
+                        return completions;
+                    }
+                    let mut rubik = Rubik::new();
+                    let placed = corners.slots.iter().zip(corner_set);
+                    for (slot, cube) in placed.chain(edges.slots.iter().zip(edge_set)) {
+                        rubik.cubes[*slot as usize] = *cube;
+                    }
+                    completions.push(rubik.compose(&held_as_shown));
+                }
+            }
+        }
+        completions
+    }
+
+    /// the state showing the known stickers if there is only one
+    pub fn complete(&self) -> Completion {
+        match self.count() {
+            0 => Completion::Impossible,
+            1 => Completion::Unique(self.completions(1).remove(0)),
+            count => Completion::Many(count),
+        }
+    }
+
+    /// the rotation of the core the known centers show, as [`Rubik::from_face_colors`]
+    /// finds it, the solved one when the centers may be home
+    fn core(&self) -> Option<Cube> {
+        std::iter::once(Cube::new())
+            .chain(CubePermutation::enumerate().map(Cube::new_with_rotation))
+            .find(|core| {
+                CubeFace::ALL.into_iter().all(|face| {
+                    let facelet = Facelet::new(face, 1, 1).expect("the center");
+                    self.facelets[facelet.index()].is_none_or(|home| home == core.get(face))
+                })
+            })
+    }
+
+    /// the known stickers as seen once the cube, its core rotated as `core`, is held with
+    /// its centers home
+    fn held(&self, core: Cube) -> Self {
+        let back = Cube::new_with_rotation(core.rotation.inverse());
+        Self::new(std::array::from_fn(|index| {
+            let (slot, face) = Facelet::from_index(index).expect("a facelet").position();
+            let shown = Facelet::from_position(back.home(slot), back.get(face))
+                .expect("a rotation keeps the faces of a slot");
+            self.facelets[shown.index()]
+        }))
+    }
+
+    /// the corners and the edges, the centers being home
+    fn pieces(&self) -> [Pieces; 2] {
+        [
+            Pieces::new(self, CubePosition::is_corner, 3),
+            Pieces::new(self, CubePosition::is_edge, 2),
+        ]
+    }
+}
+
+/// The slots of one kind of pieces, with the pieces each of them may hold.
+struct Pieces {
+    slots: Vec<CubePosition>,
+    /// for each slot, the piece (as an index in `slots`), its orientation and its cube
+    options: Vec<Vec<(usize, u8, Cube)>>,
+    modulo: u8,
+}
+
+/// completions of the slots left, for a set of used pieces and a sum of orientations
+type Memo = HashMap<(u32, u8), [u128; 2]>;
+
+impl Pieces {
+    fn new(state: &PartialState, kind: fn(CubePosition) -> bool, modulo: u8) -> Self {
+        let slots = CubePosition::all()
+            .filter(|slot| kind(*slot))
+            .collect::<Vec<_>>();
+        let options = slots
+            .iter()
+            .map(|slot| {
+                CubePermutation::enumerate()
+                    .map(Cube::new_with_rotation)
+                    .filter(|cube| {
+                        slot.faces().all(|face| {
+                            let facelet = Facelet::from_position(*slot, face).expect("a facelet");
+                            state.facelets[facelet.index()]
+                                .is_none_or(|home| home == cube.get(face))
+                        })
+                    })
+                    .filter_map(|cube| {
+                        let piece = slots.iter().position(|home| *home == cube.home(*slot))?;
+                        Some((piece, cube.orientation(*slot), cube))
+                    })
+                    .collect()
+            })
+            .collect();
+        Self {
+            slots,
+            options,
+            modulo,
+        }
+    }
+
+    /// the number of ways to fill the slots with an orientation sum of 0, by parity of
+    /// the permutation
+    fn count(&self) -> [u128; 2] {
+        self.completions(&mut HashMap::new(), 0, 0)
+    }
+
+    /// the completions once the pieces of `used` fill the first slots, their
+    /// orientations summing to `twist`, by parity of the permutation of the slots left
+    fn completions(&self, memo: &mut Memo, used: u32, twist: u8) -> [u128; 2] {
+        let index = used.count_ones() as usize;
+        if index == self.slots.len() {
+            return [(twist == 0) as u128, 0];
+        }
+        if let Some(counts) = memo.get(&(used, twist)) {
+            return *counts;
+        }
+        let mut counts = [0; 2];
+        for (piece, orientation, _) in &self.options[index] {
+            if used >> piece & 1 == 1 {
+                continue;
+            }
+            let (next, parity) = self.place(used, *piece);
+            let rest = self.completions(memo, next, (twist + orientation) % self.modulo);
+            for (rest_parity, count) in rest.into_iter().enumerate() {
+                counts[rest_parity ^ parity] += count;
+            }
+        }
+        memo.insert((used, twist), counts);
+        counts
+    }
+
+    /// the set of used pieces once `piece` fills the next slot, and the parity of the
+    /// inversions it makes with the pieces already placed
+    fn place(&self, used: u32, piece: usize) -> (u32, usize) {
+        let inversions = (used >> (piece + 1)).count_ones();
+        (used | 1 << piece, (inversions & 1) as usize)
+    }
+
+    /// at most `limit` fillings of the slots with a permutation of parity `parity`
+    fn walk(&self, parity: usize, limit: usize) -> Vec<Vec<Cube>> {
+        // `at` holds the used pieces, their orientation sum and the parity left to make
+        fn go(
+            pieces: &Pieces,
+            memo: &mut Memo,
+            (used, twist, parity): (u32, u8, usize),
+            current: &mut Vec<Cube>,
+            found: &mut Vec<Vec<Cube>>,
+            limit: usize,
+        ) {
+            if found.len() == limit {
+                return;
+            }
+            if current.len() == pieces.slots.len() {
+                found.push(current.clone());
+                return;
+            }
+            for (piece, orientation, cube) in &pieces.options[current.len()] {
+                if used >> piece & 1 == 1 {
+                    continue;
+                }
+                let (next, inversions) = pieces.place(used, *piece);
+                let twist = (twist + orientation) % pieces.modulo;
+                if pieces.completions(memo, next, twist)[parity ^ inversions] == 0 {
+                    continue;
+                }
+                current.push(*cube);
+                let at = (next, twist, parity ^ inversions);
+                go(pieces, memo, at, current, found, limit);
+                current.pop();
+            }
+        }
+        let mut found = vec![];
+        let mut memo = HashMap::new();
+        if self.completions(&mut memo, 0, 0)[parity] > 0 {
+            go(
+                self,
+                &mut memo,
+                (0, 0, parity),
+                &mut vec![],
+                &mut found,
+                limit,
+            );
+        }
+        found
+    }
+}
@@ -0,0 +1,88 @@
+//! Piece identities: the 8 corners, 12 edges and 6 centers, each named by the slot it
+//! sits in on the solved cube, and where they are on a scrambled one.
+use std::fmt::Display;
+
+use crate::{cube::CubeFace, CubePosition, Rubik};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PieceKind {
+    Corner,
+    Edge,
+    Center,
+}
+
+/// A piece of the cube, the core excepted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Piece(CubePosition);
+
+impl Piece {
+    /// the piece whose home is `home`, `None` for the core
+    pub fn new(home: CubePosition) -> Option<Self> {
+        (home != CubePosition::C).then_some(Self(home))
+    }
+
+    /// the piece showing stickers on all of `faces`, as `[U, F, R]` for the UFR corner
+    pub fn from_faces(faces: impl IntoIterator<Item = CubeFace>) -> Option<Self> {
+        CubePosition::from_faces(faces).and_then(Self::new)
+    }
+
+    pub const fn home(self) -> CubePosition {
+        self.0
+    }
+
+    pub fn kind(self) -> PieceKind {
+        match self.0.faces().count() {
+            3 => PieceKind::Corner,
+            2 => PieceKind::Edge,
+            _ => PieceKind::Center,
+        }
+    }
+
+    /// the faces the piece shows stickers of
+    pub fn faces(self) -> impl Iterator<Item = CubeFace> {
+        self.0.faces()
+    }
+
+    /// the 26 pieces, in slot order
+    pub fn all() -> impl Iterator<Item = Self> {
+        CubePosition::all().filter_map(Self::new)
+    }
+
+    pub fn corners() -> impl Iterator<Item = Self> {
+        Self::all().filter(|piece| piece.kind() == PieceKind::Corner)
+    }
+
+    pub fn edges() -> impl Iterator<Item = Self> {
+        Self::all().filter(|piece| piece.kind() == PieceKind::Edge)
+    }
+
+    pub fn centers() -> impl Iterator<Item = Self> {
+        Self::all().filter(|piece| piece.kind() == PieceKind::Center)
+    }
+}
+
+impl Display for Piece {
+    /// the faces of the piece, U/D first then F/B then L/R, as `UFR`
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for face in self.faces() {
+            write!(f, "{face:?}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Rubik {
+    /// the piece sitting at `slot`, `None` for the core
+    pub fn piece_at(&self, slot: CubePosition) -> Option<Piece> {
+        Piece::new(self.cubes[slot as usize].home(slot))
+    }
+
+    /// the slot `piece` sits at, with its twist for a corner or its flip for an edge,
+    /// see [`crate::cube::Cube::orientation`]
+    pub fn locate(&self, piece: Piece) -> (CubePosition, u8) {
+        let slot = CubePosition::all()
+            .find(|slot| self.cubes[*slot as usize].home(*slot) == piece.home())
+            .expect("every piece sits somewhere");
+        (slot, self.cubes[slot as usize].orientation(slot))
+    }
+}
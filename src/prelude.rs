@@ -1,5 +1,7 @@
 pub use crate::colored::*;
 pub use crate::cube::*;
+pub use crate::facelet::*;
 pub use crate::permutation::*;
+pub use crate::piece::*;
 pub use crate::transform::*;
-pub use crate::{Rubik, RubikLayer, RubikLayerIter};
+pub use crate::{CubePosition, Rubik, RubikLayer, RubikLayerIter};
@@ -0,0 +1,96 @@
+//! Text renderings of a cube as its unfolded net:
+//!
+//! ```text
+//!       W W W
+//!       W W W
+//!       W W W
+//! G G G R R R B B B O O O
+//! G G G R R R B B B O O O
+//! G G G R R R B B B O O O
+//!       Y Y Y
+//!       Y Y Y
+//!       Y Y Y
+//! ```
+//!
+//! U above F, then L, F, R and B side by side, and D below F, each face read as
+//! [`Facelet::of_face`] reads it. Lines end with a newline and no cursor movement is
+//! used, so renderings can be printed anywhere or compared as strings.
+//!
+//! [`svg`] draws the net and other views as SVG documents.
+pub mod svg;
+
+use crate::{
+    colored::{CubeFaceMap, RubikColor},
+    cube::CubeFace,
+    facelet::Facelet,
+    Rubik,
+};
+
+/// How a sticker is drawn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Style {
+    /// the initial of the color, as `W`
+    Ascii,
+    /// two spaces on a 24-bit background of the color, see [`RubikColor::rgb`]
+    Ansi,
+    /// a colored square, as `🟥`
+    Unicode,
+}
+
+impl Style {
+    fn sticker(self, color: RubikColor) -> String {
+        match self {
+            Style::Ascii => format!("{} ", color.letter()),
+            Style::Ansi => {
+                let [r, g, b] = color.rgb();
+                format!("\x1b[48;2;{r};{g};{b}m  \x1b[0m")
+            }
+            Style::Unicode => match color {
+                RubikColor::White => "⬜",
+                RubikColor::Yellow => "🟨",
+                RubikColor::Red => "🟥",
+                RubikColor::Orange => "🟧",
+                RubikColor::Blue => "🟦",
+                RubikColor::Green => "🟩",
+            }
+            .to_string(),
+        }
+    }
+}
+
+/// the unfolded net of `rubik` in `style`, `scheme` giving the color of each face
+pub fn net(rubik: &Rubik, scheme: &CubeFaceMap<RubikColor>, style: Style) -> String {
+    let row = |faces: &[CubeFace], row: u8| {
+        faces
+            .iter()
+            .flat_map(|face| (0..3).map(move |col| (*face, col)))
+            .map(|(face, col)| {
+                let facelet = Facelet::new(face, row, col).expect("a facelet");
+                style.sticker(scheme[rubik.facelet(facelet)])
+            })
+            .collect::<String>()
+    };
+    // every style draws a sticker two columns wide
+    let indent = " ".repeat(6);
+    let mut net = String::new();
+    let bands: [(&str, &[CubeFace]); 3] = [
+        (&indent, &[CubeFace::U]),
+        ("", &[CubeFace::L, CubeFace::F, CubeFace::R, CubeFace::B]),
+        (&indent, &[CubeFace::D]),
+    ];
+    for (indent, faces) in bands {
+        for index in 0..3 {
+            let line = format!("{indent}{}", row(faces, index));
+            net.push_str(line.trim_end());
+            net.push('\n');
+        }
+    }
+    net
+}
+
+impl std::fmt::Display for Rubik {
+    /// the net in [`Style::Ascii`] with [`RubikColor::classic_map`]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", net(self, &RubikColor::classic_map(), Style::Ascii))
+    }
+}
@@ -0,0 +1,190 @@
+//! Standalone SVG documents: the unfolded net, an isometric view of the U, F and R
+//! faces, and top views of the last layer as drawn for OLL and PLL cases.
+//!
+//! Colors are given as RGB for each face, so any scheme can be drawn; a scheme of
+//! [`RubikColor`](crate::colored::RubikColor) maps to it with
+//! `scheme.map(|color| color.rgb())`.
+use std::fmt::Write;
+
+use crate::{
+    colored::{CubeFaceMap, Rgb},
+    cube::CubeFace,
+    facelet::Facelet,
+    CubePosition, Rubik,
+};
+
+/// the side of a sticker
+const SIZE: f64 = 30.0;
+/// the space around the drawing
+const MARGIN: f64 = 10.0;
+/// the depth of the side stickers around a last layer
+const SIDE: f64 = 10.0;
+/// the space between a last layer and its side stickers
+const GAP: f64 = 3.0;
+
+/// the unfolded net, laid out as [`render::net`](super::net)
+pub fn net(rubik: &Rubik, colors: &CubeFaceMap<Rgb>) -> String {
+    let origins = [
+        (CubeFace::U, 3.0, 0.0),
+        (CubeFace::L, 0.0, 3.0),
+        (CubeFace::F, 3.0, 3.0),
+        (CubeFace::R, 6.0, 3.0),
+        (CubeFace::B, 9.0, 3.0),
+        (CubeFace::D, 3.0, 6.0),
+    ];
+    let mut body = String::new();
+    for (face, x, y) in origins {
+        for facelet in Facelet::of_face(face) {
+            let x = MARGIN + (x + facelet.col as f64) * SIZE;
+            let y = MARGIN + (y + facelet.row as f64) * SIZE;
+            let fill = colors[rubik.facelet(facelet)];
+            rect(&mut body, (x, y, SIZE, SIZE), fill);
+        }
+    }
+    document(12.0 * SIZE + 2.0 * MARGIN, 9.0 * SIZE + 2.0 * MARGIN, &body)
+}
+
+/// the U, F and R faces seen from above the front right corner
+pub fn isometric(rubik: &Rubik, colors: &CubeFaceMap<Rgb>) -> String {
+    let (cos, sin) = (30f64.to_radians().cos(), 30f64.to_radians().sin());
+    // points of the cube from (0, 0, 0) to (3, 3, 3), x towards R, y towards U and
+    // z towards F, the top back corner at the top of the drawing
+    let project = |[x, y, z]: [f64; 3]| {
+        (
+            MARGIN + (3.0 * cos + (x - z) * cos) * SIZE,
+            MARGIN + (3.0 + (x + z) * sin - y) * SIZE,
+        )
+    };
+    // the point of a face at `across` columns from its left and `down` rows from its top
+    let point = |face: CubeFace, across: f64, down: f64| match face {
+        CubeFace::U => [across, 3.0, down],
+        CubeFace::F => [across, 3.0 - down, 3.0],
+        _ => [3.0, 3.0 - down, 3.0 - across],
+    };
+    let mut body = String::new();
+    for face in [CubeFace::U, CubeFace::F, CubeFace::R] {
+        for facelet in Facelet::of_face(face) {
+            let (col, row) = (facelet.col as f64, facelet.row as f64);
+            let corners = [(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0)]
+                .map(|(across, down)| project(point(face, col + across, row + down)));
+            let fill = colors[rubik.facelet(facelet)];
+            polygon(&mut body, &corners, fill);
+        }
+    }
+    let width = 6.0 * cos * SIZE + 2.0 * MARGIN;
+    document(width, 6.0 * SIZE + 2.0 * MARGIN, &body)
+}
+
+/// the U face seen from above with B on top and the top row of the side faces around
+/// it, with arrows from each last layer piece out of place to its home if `arrows`
+pub fn last_layer(rubik: &Rubik, colors: &CubeFaceMap<Rgb>, arrows: bool) -> String {
+    let start = MARGIN + SIDE + GAP;
+    let end = start + 3.0 * SIZE;
+    let mut body = String::new();
+    for facelet in Facelet::of_face(CubeFace::U) {
+        let x = start + facelet.col as f64 * SIZE;
+        let y = start + facelet.row as f64 * SIZE;
+        let fill = colors[rubik.facelet(facelet)];
+        rect(&mut body, (x, y, SIZE, SIZE), fill);
+    }
+    // the B stickers are seen from behind and the L ones from the left
+    for index in 0..3u8 {
+        let along = index as f64 * SIZE;
+        let back = (2 - index) as f64 * SIZE;
+        let sides = [
+            (CubeFace::F, (start + along, end + GAP, SIZE, SIDE)),
+            (CubeFace::B, (start + back, MARGIN, SIZE, SIDE)),
+            (CubeFace::R, (end + GAP, start + back, SIDE, SIZE)),
+            (CubeFace::L, (MARGIN, start + along, SIDE, SIZE)),
+        ];
+        for (face, area) in sides {
+            rect(&mut body, area, colors[rubik.sticker(face, 0, index)]);
+        }
+    }
+    if arrows {
+        arrows_of(rubik, start, &mut body);
+    }
+    let side = 3.0 * SIZE + 2.0 * (MARGIN + SIDE + GAP);
+    document(side, side, &body)
+}
+
+/// arrows between the centers of the U stickers of the last layer pieces out of place,
+/// a swap drawn as one arrow with two heads
+fn arrows_of(rubik: &Rubik, start: f64, body: &mut String) {
+    let center = |slot: CubePosition| {
+        let facelet = Facelet::from_position(slot, CubeFace::U).expect("a U facelet");
+        (
+            start + (facelet.col as f64 + 0.5) * SIZE,
+            start + (facelet.row as f64 + 0.5) * SIZE,
+        )
+    };
+    let moves = CubePosition::all()
+        .filter(|slot| slot.faces().any(|face| face == CubeFace::U))
+        .filter_map(|slot| {
+            let home = rubik.piece_at(slot)?.home();
+            let in_layer = home.faces().any(|face| face == CubeFace::U);
+            (in_layer && home != slot).then_some((slot, home))
+        })
+        .collect::<Vec<_>>();
+    let _ = writeln!(
+        body,
+        r#"<defs><marker id="head" viewBox="0 0 10 10" refX="8" refY="5" markerWidth="4" markerHeight="4" orient="auto-start-reverse"><path d="M 0 0 L 10 5 L 0 10 z"/></marker></defs>"#
+    );
+    for (from, to) in &moves {
+        let swap = moves.contains(&(*to, *from));
+        if swap && *from as u8 > *to as u8 {
+            continue;
+        }
+        let ((x1, y1), (x2, y2)) = (center(*from), center(*to));
+        // stop short of the centers so that arrows meeting there stay apart
+        let length = (x2 - x1).hypot(y2 - y1);
+        let (dx, dy) = (
+            (x2 - x1) / length * SIZE / 4.0,
+            (y2 - y1) / length * SIZE / 4.0,
+        );
+        let heads = if swap {
+            r#" marker-start="url(#head)" marker-end="url(#head)""#
+        } else {
+            r#" marker-end="url(#head)""#
+        };
+        let _ = writeln!(
+            body,
+            r#"<line x1="{:.2}" y1="{:.2}" x2="{:.2}" y2="{:.2}" stroke="black" stroke-width="3"{heads}/>"#,
+            x1 + dx,
+            y1 + dy,
+            x2 - dx,
+            y2 - dy
+        );
+    }
+}
+
+fn document(width: f64, height: f64, body: &str) -> String {
+    format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width:.2}\" height=\"{height:.2}\" viewBox=\"0 0 {width:.2} {height:.2}\">\n{body}</svg>\n"
+    )
+}
+
+fn fill([r, g, b]: Rgb) -> String {
+    format!("#{r:02x}{g:02x}{b:02x}")
+}
+
+fn rect(body: &mut String, (x, y, width, height): (f64, f64, f64, f64), color: Rgb) {
+    let _ = writeln!(
+        body,
+        r#"<rect x="{x:.2}" y="{y:.2}" width="{width:.2}" height="{height:.2}" fill="{}" stroke="black" stroke-width="2"/>"#,
+        fill(color)
+    );
+}
+
+fn polygon(body: &mut String, corners: &[(f64, f64)], color: Rgb) {
+    let points = corners
+        .iter()
+        .map(|(x, y)| format!("{x:.2},{y:.2}"))
+        .collect::<Vec<_>>()
+        .join(" ");
+    let _ = writeln!(
+        body,
+        r#"<polygon points="{points}" fill="{}" stroke="black" stroke-width="2" stroke-linejoin="round"/>"#,
+        fill(color)
+    );
+}
@@ -0,0 +1,221 @@
+//! Scanned input: the average RGB colors of the 54 stickers, as measured on photos of a
+//! cube, classified into the six colors.
+//!
+//! Colors are compared in CIELAB, where euclidean distances follow perceived color
+//! differences. The centers anchor the six groups and every group gets exactly nine
+//! stickers, so that a sticker closer to another color can still be placed right when
+//! that color is full.
+use crate::{
+    colored::{CubeFaceMap, RubikColor},
+    facelet::{ColorInputError, Facelet},
+    Rubik,
+};
+
+pub use crate::colored::Rgb;
+
+/// The classified stickers, in [`Facelet`] index order.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Scan {
+    pub colors: [RubikColor; 54],
+    /// how much closer the sticker is to its color than to any other, from 0 (as close
+    /// to another color) to 1 (right on its color), always 1 for centers
+    pub confidence: [f64; 54],
+}
+
+impl Scan {
+    /// the stickers whose confidence is below `threshold`, to be checked by hand
+    pub fn ambiguous(&self, threshold: f64) -> Vec<Facelet> {
+        Facelet::all()
+            .filter(|facelet| self.confidence[facelet.index()] < threshold)
+            .collect()
+    }
+
+    /// the color letters of each face, as read by [`Rubik::from_face_colors`]
+    pub fn faces(&self) -> CubeFaceMap<String> {
+        let face = |index: usize| {
+            self.colors[index * 9..index * 9 + 9]
+                .iter()
+                .map(|color| color.letter())
+                .collect::<String>()
+        };
+        let [u, r, f, d, l, b] = [0, 1, 2, 3, 4, 5].map(face);
+        CubeFaceMap::new(f, r, u, b, l, d)
+    }
+
+    /// the state showing the classified colors, `scheme` giving the color of each face
+    pub fn rubik(&self, scheme: &CubeFaceMap<RubikColor>) -> Result<Rubik, ColorInputError> {
+        let faces = self.faces();
+        let faces = CubeFaceMap::new(
+            faces.f.as_str(),
+            faces.r.as_str(),
+            faces.u.as_str(),
+            faces.b.as_str(),
+            faces.l.as_str(),
+            faces.d.as_str(),
+        );
+        Rubik::from_face_colors_with_scheme(&faces, scheme)
+    }
+}
+
+/// classify the colors of the 54 stickers given in [`Facelet`] index order
+pub fn classify(rgb: &[Rgb; 54]) -> Scan {
+    let colors = rgb.map(lab);
+    let centers = [0, 1, 2, 3, 4, 5].map(|group| group * 9 + 4);
+
+    // the colors of the centers, matched as a whole to the typical colors
+    let cost = centers
+        .iter()
+        .map(|center| {
+            RubikColor::ALL
+                .iter()
+                .map(|color| distance(&colors[*center], &lab(color.rgb())))
+                .collect()
+        })
+        .collect::<Vec<_>>();
+    let center_colors = assignment(&cost)
+        .expect("a square cost matrix")
+        .into_iter()
+        .map(|color| RubikColor::ALL[color])
+        .collect::<Vec<_>>();
+
+    // nine stickers per group, anchored on the centers then on the mean of each group
+    let others = (0..54)
+        .filter(|index| !centers.contains(index))
+        .collect::<Vec<_>>();
+    let mut anchors = centers.map(|center| colors[center]);
+    let mut groups = [0; 54];
+    for _ in 0..2 {
+        let cost = others
+            .iter()
+            .map(|sticker| {
+                (0..48)
+                    .map(|slot| distance(&colors[*sticker], &anchors[slot / 8]))
+                    .collect()
+            })
+            .collect::<Vec<_>>();
+        let slots = assignment(&cost).expect("a square cost matrix");
+        for (sticker, slot) in others.iter().zip(slots) {
+            groups[*sticker] = slot / 8;
+        }
+        for (group, center) in centers.iter().enumerate() {
+            groups[*center] = group;
+        }
+        anchors = std::array::from_fn(|group| {
+            let members = (0..54).filter(|index| groups[*index] == group);
+            let sum = members.fold([0.0; 3], |sum, index| {
+                std::array::from_fn(|i| sum[i] + colors[index][i])
+            });
+            sum.map(|value| value / 9.0)
+        });
+    }
+
+    let confidence = std::array::from_fn(|index| {
+        if centers.contains(&index) {
+            return 1.0;
+        }
+        let own = distance(&colors[index], &anchors[groups[index]]);
+        let other = (0..6)
+            .filter(|group| *group != groups[index])
+            .map(|group| distance(&colors[index], &anchors[group]))
+            .fold(f64::INFINITY, f64::min);
+        if own + other > 0.0 {
+            ((other - own) / (other + own)).clamp(0.0, 1.0)
+        } else {
+            0.0
+        }
+    });
+    Scan {
+        colors: groups.map(|group| center_colors[group]),
+        confidence,
+    }
+}
+
+/// the CIELAB coordinates of an sRGB color, under the D65 illuminant
+fn lab(rgb: Rgb) -> [f64; 3] {
+    let linear = rgb.map(|channel| {
+        let channel = channel as f64 / 255.0;
+        if channel <= 0.04045 {
+            channel / 12.92
+        } else {
+            ((channel + 0.055) / 1.055).powf(2.4)
+        }
+    });
+    let [r, g, b] = linear;
+    let xyz = [
+        (0.4124 * r + 0.3576 * g + 0.1805 * b) / 0.95047,
+        0.2126 * r + 0.7152 * g + 0.0722 * b,
+        (0.0193 * r + 0.1192 * g + 0.9505 * b) / 1.08883,
+    ];
+    let [x, y, z] = xyz.map(|value| {
+        if value > 216.0 / 24389.0 {
+            value.cbrt()
+        } else {
+            (24389.0 / 27.0 * value + 16.0) / 116.0
+        }
+    });
+    [116.0 * y - 16.0, 500.0 * (x - y), 200.0 * (y - z)]
+}
+
+/// the CIE76 color difference
+fn distance(a: &[f64; 3], b: &[f64; 3]) -> f64 {
+    (0..3).map(|i| (a[i] - b[i]).powi(2)).sum::<f64>().sqrt()
+}
+
+/// the column of each row in an assignment of least total cost, by the Hungarian
+/// method, `None` unless `cost` is square
+fn assignment(cost: &[Vec<f64>]) -> Option<Vec<usize>> {
+    let n = cost.len();
+    if cost.iter().any(|row| row.len() != n) {
+        return None;
+    }
+    // potentials of rows and columns, and the row matched to each column, from 1
+    let (mut u, mut v) = (vec![0.0; n + 1], vec![0.0; n + 1]);
+    let (mut matched, mut way) = (vec![0; n + 1], vec![0; n + 1]);
+    for row in 1..=n {
+        matched[0] = row;
+        let mut column = 0;
+        let mut min = vec![f64::INFINITY; n + 1];
+        let mut used = vec![false; n + 1];
+        loop {
+            used[column] = true;
+            let current = matched[column];
+            let mut delta = f64::INFINITY;
+            let mut next = 0;
+            for j in 1..=n {
+                if !used[j] {
+                    let reduced = cost[current - 1][j - 1] - u[current] - v[j];
+                    if reduced < min[j] {
+                        min[j] = reduced;
+                        way[j] = column;
+                    }
+                    if min[j] < delta {
+                        delta = min[j];
+                        next = j;
+                    }
+                }
+            }
+            for j in 0..=n {
+                if used[j] {
+                    u[matched[j]] += delta;
+                    v[j] -= delta;
+                } else {
+                    min[j] -= delta;
+                }
+            }
+            column = next;
+            if matched[column] == 0 {
+                break;
+            }
+        }
+        while column != 0 {
+            let previous = way[column];
+            matched[column] = matched[previous];
+            column = previous;
+        }
+    }
+    let mut columns = vec![0; n];
+    for j in 1..=n {
+        columns[matched[j] - 1] = j - 1;
+    }
+    Some(columns)
+}
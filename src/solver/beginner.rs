@@ -0,0 +1,287 @@
+//! Layer by layer solving as taught to beginners: cross, first layer corners,
+//! middle layer edges, last layer cross, last layer orientation and permutation.
+use std::sync::Arc;
+
+use crate::{
+    cube::CubeFace,
+    prelude::RubikLayerTransform::{self as T},
+    CubePosition, Rubik,
+};
+
+use super::{
+    enumerate::HTM,
+    mask::{GoalMask, MaskSolver},
+    steps::{collect_steps, invert, macro_step, notation, rotate_y, Alg, Macro, Step},
+    RubikSolveState, RubikSolver,
+};
+
+pub const SEXY_MOVE: Alg = Alg::new("sexy move", &[&T::R, &T::U, &T::RI, &T::UI]);
+pub const RIGHT_INSERT: Alg = Alg::new(
+    "right insert",
+    &[&T::U, &T::R, &T::UI, &T::RI, &T::UI, &T::FI, &T::U, &T::F],
+);
+pub const LEFT_INSERT: Alg = Alg::new(
+    "left insert",
+    &[&T::UI, &T::LI, &T::U, &T::L, &T::U, &T::F, &T::UI, &T::FI],
+);
+pub const LINE_TO_CROSS: Alg = Alg::new("line", &[&T::F, &T::R, &T::U, &T::RI, &T::UI, &T::FI]);
+pub const L_TO_CROSS: Alg = Alg::new("L shape", &[&T::F, &T::U, &T::R, &T::UI, &T::RI, &T::FI]);
+pub const SUNE: Alg = Alg::new(
+    "Sune",
+    &[&T::R, &T::U, &T::RI, &T::U, &T::R, &T::U2, &T::RI],
+);
+pub const ANTISUNE: Alg = Alg::new(
+    "Anti-Sune",
+    &[&T::R, &T::U2, &T::RI, &T::UI, &T::R, &T::UI, &T::RI],
+);
+pub const A_PERM: Alg = Alg::new(
+    "A perm",
+    &[
+        &T::RI,
+        &T::F,
+        &T::RI,
+        &T::B2,
+        &T::R,
+        &T::FI,
+        &T::RI,
+        &T::B2,
+        &T::R2,
+    ],
+);
+pub const UA_PERM: Alg = Alg::new(
+    "Ua perm",
+    &[
+        &T::R,
+        &T::UI,
+        &T::R,
+        &T::U,
+        &T::R,
+        &T::U,
+        &T::R,
+        &T::UI,
+        &T::RI,
+        &T::UI,
+        &T::R2,
+    ],
+);
+pub const UB_PERM: Alg = Alg::new(
+    "Ub perm",
+    &[
+        &T::R2,
+        &T::U,
+        &T::R,
+        &T::U,
+        &T::RI,
+        &T::UI,
+        &T::RI,
+        &T::UI,
+        &T::RI,
+        &T::U,
+        &T::RI,
+    ],
+);
+
+/// Solves the cube layer by layer, starting with a cross on D, explaining every step.
+///
+/// The centers must be in their home slots, as after any scramble of face turns.
+pub struct BeginnerSolver;
+
+/// the D layer slots in the order they are solved, with the `y` rotations
+/// bringing the front right slot onto them
+const SLOTS: [(CubeFace, CubeFace, usize); 4] = [
+    (CubeFace::F, CubeFace::R, 0),
+    (CubeFace::R, CubeFace::B, 1),
+    (CubeFace::B, CubeFace::L, 2),
+    (CubeFace::L, CubeFace::F, 3),
+];
+
+fn position(faces: impl IntoIterator<Item = CubeFace>) -> CubePosition {
+    CubePosition::from_faces(faces).expect("faces of a slot are never opposite")
+}
+
+fn u_layer() -> impl Iterator<Item = CubePosition> {
+    CubePosition::all().filter(|p| p.faces().any(|f| f == CubeFace::U))
+}
+
+fn auf_macros() -> Vec<Macro> {
+    [&T::U, &T::U2, &T::UI]
+        .into_iter()
+        .map(|op| Macro::new(op.to_string(), vec![op]))
+        .collect()
+}
+
+impl BeginnerSolver {
+    pub fn steps(&self, mut rubik: Rubik) -> Vec<Step> {
+        let mut steps = vec![];
+
+        let mut mask = GoalMask::new().solve([position([CubeFace::D])]);
+        for (side, _, _) in SLOTS {
+            let edge = position([CubeFace::D, side]);
+            mask = mask.solve([edge]);
+            let (solved, ops) = MaskSolver::htm(&mask).solve(rubik).collect();
+            rubik = solved;
+            steps.push(Step {
+                name: format!("cross: D{side:?} edge"),
+                explanation: format!(
+                    "Bring the edge between the D and {side:?} centers down so it matches both, \
+                     without breaking the cross edges already placed."
+                ),
+                ops,
+            });
+        }
+
+        for (front, right, y) in SLOTS {
+            let corner = position([CubeFace::D, front, right]);
+            mask = mask.solve([corner]);
+            let macros = (1..=5)
+                .flat_map(|times| {
+                    let ops = (0..times)
+                        .flat_map(|_| SEXY_MOVE.moves.iter().map(|op| rotate_y(op, y)))
+                        .collect::<Vec<_>>();
+                    let name = format!("{} x{times}", notation(ops[..4].iter().copied()));
+                    Macro::with_setup(CubeFace::U, &name, &ops)
+                })
+                .collect::<Vec<_>>();
+            let pop_out = SLOTS
+                .iter()
+                .map(|(_, _, y)| {
+                    let ops = SEXY_MOVE
+                        .moves
+                        .iter()
+                        .map(|op| rotate_y(op, *y))
+                        .collect::<Vec<_>>();
+                    Macro::new(
+                        format!("{} to take the corner out", notation(ops.iter().copied())),
+                        ops,
+                    )
+                })
+                .collect::<Vec<_>>();
+            let goal = mask.clone();
+            steps.push(macro_step(
+                &mut rubik,
+                format!("first layer: D{front:?}{right:?} corner"),
+                &format!(
+                    "Turn U until the corner is above its slot, between the {front:?} and {right:?} centers, \
+                     then repeat the sexy move seen from that slot until the corner is solved."
+                ),
+                &[pop_out, macros].concat(),
+                2,
+                |rubik| goal.is_reached(rubik),
+            ));
+        }
+
+        let inserts = [RIGHT_INSERT, LEFT_INSERT]
+            .iter()
+            .flat_map(Macro::rotations_y)
+            .flat_map(|m| Macro::with_setup(CubeFace::U, &m.name, &m.ops))
+            .collect::<Vec<_>>();
+        for (front, right, _) in SLOTS {
+            let edge = position([front, right]);
+            mask = mask.solve([edge]);
+            let goal = mask.clone();
+            steps.push(macro_step(
+                &mut rubik,
+                format!("middle layer: {front:?}{right:?} edge"),
+                &format!(
+                    "Find the edge in the U layer, turn U until its side color matches a center, \
+                     then insert it left or right into the slot between {front:?} and {right:?}."
+                ),
+                &inserts,
+                2,
+                |rubik| goal.is_reached(rubik),
+            ));
+        }
+
+        let edges = u_layer().filter(|p| p.is_edge());
+        let cross = mask.clone().orient(edges);
+        let macros = [LINE_TO_CROSS, L_TO_CROSS]
+            .iter()
+            .flat_map(Macro::with_auf)
+            .collect::<Vec<_>>();
+        steps.push(macro_step(
+            &mut rubik,
+            "last layer cross".to_string(),
+            "Orient the U edges to make a cross on top: a line needs F R U R' U' F', \
+             an L shape F U R U' R' F', a dot both.",
+            &macros,
+            3,
+            |rubik| cross.is_reached(rubik),
+        ));
+
+        let oriented = mask.clone().orient(u_layer());
+        let macros = [SUNE, ANTISUNE]
+            .iter()
+            .flat_map(Macro::with_auf)
+            .collect::<Vec<_>>();
+        steps.push(macro_step(
+            &mut rubik,
+            "orient last layer".to_string(),
+            "Turn the corners so the whole top face has the U color, using Sune and Anti-Sune.",
+            &macros,
+            3,
+            |rubik| oriented.is_reached(rubik),
+        ));
+
+        let corners = mask.clone().solve(u_layer().filter(|p| p.is_corner()));
+        let aligned = |goal: &GoalMask, rubik: &Rubik| {
+            [&T::UNIT, &T::U, &T::U2, &T::UI].iter().any(|auf| {
+                let mut rubik = rubik.clone();
+                auf.apply_on(&mut rubik);
+                goal.is_reached(&rubik)
+            })
+        };
+        let a_perm_inverse = invert(A_PERM.moves);
+        let macros = [
+            Macro::with_auf(&A_PERM),
+            Macro::with_setup(
+                CubeFace::U,
+                &format!(
+                    "inverse A perm ({})",
+                    notation(a_perm_inverse.iter().copied())
+                ),
+                &a_perm_inverse,
+            ),
+        ]
+        .concat();
+        steps.push(macro_step(
+            &mut rubik,
+            "permute last layer corners".to_string(),
+            "Put the corners in place relative to each other with the A perm, \
+             starting with the matching pair (headlights) at the back.",
+            &macros,
+            2,
+            |rubik| aligned(&corners, rubik),
+        ));
+
+        let solved = GoalMask::solved();
+        let macros = [UA_PERM, UB_PERM]
+            .iter()
+            .flat_map(Macro::with_auf)
+            .collect::<Vec<_>>();
+        steps.push(macro_step(
+            &mut rubik,
+            "permute last layer edges".to_string(),
+            "Cycle the edges with the U perms, starting with the solved edge at the back.",
+            &macros,
+            2,
+            |rubik| aligned(&solved, rubik),
+        ));
+
+        steps.push(macro_step(
+            &mut rubik,
+            "adjust U face".to_string(),
+            "Turn U to line the last layer up with the centers.",
+            &auf_macros(),
+            1,
+            Rubik::is_solved,
+        ));
+        steps
+    }
+}
+
+impl RubikSolver for BeginnerSolver {
+    fn solve(&self, rubik: Rubik) -> RubikSolveState {
+        let steps = self.steps(rubik.clone());
+        collect_steps(rubik, Arc::new(HTM), &steps)
+    }
+}
@@ -0,0 +1,144 @@
+//! CFOP (Fridrich) solving: cross, four F2L pairs, OLL and PLL.
+use std::sync::Arc;
+
+use crate::{cube::CubeFace, prelude::RubikLayerTransform as T, CubePosition, Rubik};
+
+use super::{
+    enumerate::HTM,
+    last_layer::{is_aligned, recognize_oll, recognize_pll, AUF},
+    mask::{GoalMask, MaskSolver},
+    steps::{collect_steps, Step},
+    RubikMultiSolver, RubikSolveState, RubikSolver,
+};
+
+/// Solves the cube with the cross on D and the last layer on U, annotating every stage.
+///
+/// The cross and each F2L pair are solved optimally, always taking the pair that
+/// needs the fewest moves next (FR, FL, BL then BR on ties). OLL and PLL name the
+/// recognized case and the algorithm used. The centers must be in their home slots, as after any scramble of face turns.
+pub struct CfopSolver;
+
+/// the F2L slots, as their D corner and their middle layer edge
+const SLOTS: [(CubePosition, CubePosition); 4] = [
+    (CubePosition::FDR, CubePosition::FR),
+    (CubePosition::FDL, CubePosition::FL),
+    (CubePosition::BDL, CubePosition::BL),
+    (CubePosition::BDR, CubePosition::BR),
+];
+
+fn slot_name(edge: CubePosition) -> String {
+    edge.faces().map(|face| format!("{face:?}")).collect()
+}
+
+impl CfopSolver {
+    pub fn steps(&self, rubik: Rubik) -> Vec<Step> {
+        let mut steps = vec![];
+
+        let mut mask = GoalMask::cross(CubeFace::D);
+        let (mut rubik, ops) = MaskSolver::htm(&mask).solve(rubik).collect();
+        steps.push(Step {
+            name: "cross".to_string(),
+            explanation: "Solve the four D edges against their centers.".to_string(),
+            ops,
+        });
+
+        let mut slots = SLOTS.to_vec();
+        while !slots.is_empty() {
+            // deepen the searches of all slots together, so that only the easiest
+            // pair is searched to its full length
+            let mut solvers = slots
+                .iter()
+                .map(|(corner, _)| {
+                    let goal = mask.clone().union(&GoalMask::pair(CubeFace::D, *corner));
+                    let solver = MaskSolver::htm(&goal);
+                    (goal, solver)
+                })
+                .collect::<Vec<_>>();
+            let (index, goal, (solved, ops)) = (0..=20)
+                .find_map(|len| {
+                    solvers
+                        .iter_mut()
+                        .enumerate()
+                        .find_map(|(index, (goal, solver))| {
+                            solver.set_max_len(len);
+                            let state = solver.solutions(rubik.clone()).next()?;
+                            Some((index, goal.clone(), state.collect()))
+                        })
+                })
+                .expect("every pair can be solved");
+            let (corner, edge) = slots.remove(index);
+            let slot = slot_name(edge);
+            rubik = solved;
+            mask = goal;
+            steps.push(Step {
+                name: format!("F2L {}: {slot} pair", 4 - slots.len()),
+                explanation: format!(
+                    "Pair the {corner:?} corner with the {slot} edge and insert them together, \
+                     the pair needing the fewest moves among the {} slots left.",
+                    slots.len() + 1
+                ),
+                ops,
+            });
+        }
+
+        let (explanation, ops) = match recognize_oll(&rubik) {
+            Some(case) => (
+                format!(
+                    "Case {}: orient the last layer with {}.",
+                    case.alg.name,
+                    case.notation()
+                ),
+                case.ops(),
+            ),
+            None => (
+                "OLL skip: the last layer is already oriented.".to_string(),
+                vec![],
+            ),
+        };
+        ops.iter().for_each(|op| op.apply_on(&mut rubik));
+        steps.push(Step {
+            name: "OLL".to_string(),
+            explanation,
+            ops,
+        });
+
+        let (mut explanation, mut ops) = match recognize_pll(&rubik) {
+            Some(case) => (
+                format!(
+                    "Case {}: permute the last layer with {}.",
+                    case.alg.name,
+                    case.notation()
+                ),
+                case.ops(),
+            ),
+            None => (
+                "PLL skip: the last layer is already permuted.".to_string(),
+                vec![],
+            ),
+        };
+        ops.iter().for_each(|op| op.apply_on(&mut rubik));
+        debug_assert!(is_aligned(&rubik));
+        if let Some(auf) = AUF[1..].iter().copied().find(|auf| {
+            let mut rubik = rubik.clone();
+            auf.apply_on(&mut rubik);
+            rubik.is_solved()
+        }) {
+            auf.apply_on(&mut rubik);
+            explanation.push_str(&format!(" Finish with {auf} to align the last layer."));
+            ops = T::cancel(ops.into_iter().chain([auf]));
+        }
+        steps.push(Step {
+            name: "PLL".to_string(),
+            explanation,
+            ops,
+        });
+        steps
+    }
+}
+
+impl RubikSolver for CfopSolver {
+    fn solve(&self, rubik: Rubik) -> RubikSolveState {
+        let steps = self.steps(rubik.clone());
+        collect_steps(rubik, Arc::new(HTM), &steps)
+    }
+}
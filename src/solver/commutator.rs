@@ -0,0 +1,225 @@
+//! 3-style commutators: the commutators `[A, B]` and conjugated commutators
+//! `[C: [A, B]]` cycling three corner or three edge stickers, as used in blindfolded
+//! solving.
+//!
+//! One of `A` and `B` is the interchange, a single move swapping two of the stickers,
+//! the other the insertion, a single move or a move conjugated by another
+//! (`X Y X'`) bringing the third sticker into the place of one of them.
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Display,
+};
+
+use crate::{
+    bld::Sticker, cube::Cube, permutation::CubePermutation, prelude::RubikLayerTransform as T,
+    CubePosition, Rubik,
+};
+
+use super::steps::{invert, notation, Metric};
+
+/// A conjugated commutator `[setup: [a, b]]`, played `setup a b a' b' setup'`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Commutator {
+    pub setup: Vec<&'static T>,
+    pub insertion: Vec<&'static T>,
+    pub interchange: Vec<&'static T>,
+    /// whether `a` is the interchange and `b` the insertion
+    pub interchange_first: bool,
+}
+
+impl Commutator {
+    /// `a` and `b` of `[a, b]`
+    pub fn parts(&self) -> (&[&'static T], &[&'static T]) {
+        if self.interchange_first {
+            (&self.interchange, &self.insertion)
+        } else {
+            (&self.insertion, &self.interchange)
+        }
+    }
+
+    /// the moves of the commutator once expanded and cancelled
+    pub fn moves(&self) -> Vec<&'static T> {
+        let (a, b) = self.parts();
+        let mut moves = self.setup.clone();
+        moves.extend(a.iter().chain(b));
+        moves.extend(invert(a));
+        moves.extend(invert(b));
+        moves.extend(invert(&self.setup));
+        T::cancel(moves)
+    }
+
+    pub fn move_count(&self, metric: Metric) -> usize {
+        metric.count(&self.moves())
+    }
+}
+
+impl Display for Commutator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (a, b) = self.parts();
+        let pure = format!(
+            "[{}, {}]",
+            notation(a.iter().copied()),
+            notation(b.iter().copied())
+        );
+        if self.setup.is_empty() {
+            write!(f, "{pure}")
+        } else {
+            write!(f, "[{}: {pure}]", notation(self.setup.iter().copied()))
+        }
+    }
+}
+
+/// Finds the commutators cycling three stickers, by conjugating the wanted cycle with
+/// every setup and looking the result up among the pure commutators.
+pub struct CommutatorFinder {
+    moves: Vec<&'static T>,
+    max_setup: usize,
+    pure: HashMap<Rubik, Vec<Commutator>>,
+}
+
+impl Default for CommutatorFinder {
+    /// face and slice turns, setups of at most two moves
+    fn default() -> Self {
+        Self::new(&T::ALL, 2)
+    }
+}
+
+impl CommutatorFinder {
+    /// the pure commutators of `moves` cycling three pieces, conjugated by setups of
+    /// at most `max_setup` moves
+    pub fn new(moves: &[&'static T], max_setup: usize) -> Self {
+        let insertions = moves
+            .iter()
+            .map(|x| vec![*x])
+            .chain(moves.iter().flat_map(|x| {
+                moves
+                    .iter()
+                    .filter(|y| y.layer().axis() != x.layer().axis())
+                    .map(|y| vec![*x, *y, T::from_layer(x.layer(), x.ptr_rotate().inverse())])
+            }));
+        let mut pure = HashMap::<Rubik, Vec<Commutator>>::new();
+        for insertion in insertions {
+            for interchange in moves {
+                // single moves are taken as the insertion, so that each pair is listed once
+                let orders: &[bool] = if insertion.len() == 1 {
+                    &[false]
+                } else {
+                    &[false, true]
+                };
+                for interchange_first in orders {
+                    let commutator = Commutator {
+                        setup: vec![],
+                        insertion: insertion.clone(),
+                        interchange: vec![*interchange],
+                        interchange_first: *interchange_first,
+                    };
+                    let Some(effect) = effect(&commutator.moves()) else {
+                        continue;
+                    };
+                    let moved = effect.cubes.iter().filter(|cube| **cube != Cube::new());
+                    if moved.count() == 3 {
+                        pure.entry(effect).or_default().push(commutator);
+                    }
+                }
+            }
+        }
+        Self {
+            moves: moves.to_vec(),
+            max_setup,
+            pure,
+        }
+    }
+
+    /// every commutator moving the piece showing `a` so that it shows the same sticker
+    /// on the face of `b`, that of `b` to `c` and that of `c` to `a`, leaving the other
+    /// pieces alone, the shortest in [`Metric::Stm`] first.
+    ///
+    /// Panics if the stickers are not all corner or all edge stickers of three
+    /// different pieces.
+    pub fn find(&self, a: Sticker, b: Sticker, c: Sticker) -> Vec<Commutator> {
+        let target = three_cycle([a, b, c]).expect("stickers of three corners or three edges");
+        let mut found = vec![];
+        let mut setups = vec![vec![]];
+        let mut level = vec![vec![]];
+        for _ in 0..self.max_setup {
+            level = level
+                .iter()
+                .flat_map(|setup: &Vec<&'static T>| {
+                    self.moves
+                        .iter()
+                        .filter(|op| {
+                            setup
+                                .last()
+                                .is_none_or(|last| last.layer().axis() != op.layer().axis())
+                        })
+                        .map(|op| {
+                            let mut setup = setup.clone();
+                            setup.push(*op);
+                            setup
+                        })
+                })
+                .collect();
+            setups.extend(level.iter().cloned());
+        }
+        for setup in setups {
+            let Some(conjugate) = effect(&setup) else {
+                continue;
+            };
+            // played before the commutator and undone after, the setup conjugates its effect
+            let needed = conjugate.inverse().compose(&target).compose(&conjugate);
+            for commutator in self.pure.get(&needed).into_iter().flatten() {
+                found.push(Commutator {
+                    setup: setup.clone(),
+                    ..commutator.clone()
+                });
+            }
+        }
+        found.sort_by_cached_key(|commutator| {
+            (
+                commutator.move_count(Metric::Stm),
+                commutator.setup.len(),
+                commutator.to_string(),
+            )
+        });
+        // the same moves may read as several commutators, the first one is kept
+        let mut seen = HashSet::new();
+        found.retain(|commutator| seen.insert(commutator.moves()));
+        found
+    }
+}
+
+/// the state reached from the solved cube by `moves`, `None` if they leave centers moved
+fn effect(moves: &[&'static T]) -> Option<Rubik> {
+    let mut rubik = Rubik::new();
+    moves.iter().for_each(|op| op.apply_on(&mut rubik));
+    CubePosition::all()
+        .filter(|slot| slot.is_center())
+        .all(|slot| rubik.cubes[slot as usize].home(slot) == slot)
+        .then(|| rubik.normalized())
+}
+
+/// the state where the piece showing each sticker shows it on the face of the next one
+fn three_cycle(stickers: [Sticker; 3]) -> Option<Rubik> {
+    let [(first, _), (second, _), (third, _)] = stickers;
+    let valid = stickers
+        .iter()
+        .all(|(slot, face)| slot.faces().any(|f| f == *face))
+        && first != second
+        && second != third
+        && third != first
+        && (stickers.iter().all(|(slot, _)| slot.is_corner())
+            || stickers.iter().all(|(slot, _)| slot.is_edge()));
+    if !valid {
+        return None;
+    }
+    let mut rubik = Rubik::new();
+    for (index, (from, from_face)) in stickers.into_iter().enumerate() {
+        let (to, to_face) = stickers[(index + 1) % 3];
+        let cube = CubePermutation::enumerate()
+            .map(Cube::new_with_rotation)
+            .find(|cube| cube.home(to) == from && cube.get(to_face) == from_face)
+            .expect("a rotation bringing any sticker onto any sticker of a piece of its kind");
+        rubik.cubes[to as usize] = cube;
+    }
+    Some(rubik)
+}
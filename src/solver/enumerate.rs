@@ -0,0 +1,137 @@
+use std::sync::Arc;
+
+use crate::{prelude::RubikLayerTransform, Rubik};
+
+use super::{Goal, RubikMultiSolver, RubikSolveState, RubikSolver};
+
+/// Enumerates every solution up to `max_len` moves, in non-decreasing move count.
+///
+/// Sequences trivially equivalent to another one (see [`RubikSolveState::is_trivially_equivalent`])
+/// are skipped, so `R L` is yielded but not `L R`, and so are sequences reaching the goal before
+/// their last move, a shorter solution padded. Take the first K solutions with [`Iterator::take`].
+///
+/// [`RubikSolver::solve`] returns the cube unsolved, with no move, when it has no solution
+/// within `max_len` moves, as most scrambles past a few moves. Check the goal on the
+/// result, or use [`RubikMultiSolver::solutions`], its iterator ending empty.
+pub struct EnumerateSolver {
+    pub op_set: Arc<[&'static RubikLayerTransform]>,
+    pub max_len: usize,
+    goal: Arc<dyn Goal>,
+}
+
+impl EnumerateSolver {
+    pub fn new(op_set: Arc<[&'static RubikLayerTransform]>, max_len: usize) -> Self {
+        Self {
+            op_set,
+            max_len,
+            goal: Arc::new(Rubik::is_solved as fn(&Rubik) -> bool),
+        }
+    }
+    /// all quarter and half turns of the six faces
+    pub fn htm(max_len: usize) -> Self {
+        Self::new(Arc::new(HTM), max_len)
+    }
+    /// stop on `checker` instead of [`Rubik::is_solved`]
+    pub fn with_checker(self, checker: fn(&Rubik) -> bool) -> Self {
+        self.with_goal(checker)
+    }
+    /// stop on `goal` instead of [`Rubik::is_solved`], pruning with its [`Goal::dist`]
+    pub fn with_goal(mut self, goal: impl Goal + 'static) -> Self {
+        self.goal = Arc::new(goal);
+        self
+    }
+}
+
+pub const HTM: [&RubikLayerTransform; 18] = [
+    &RubikLayerTransform::U,
+    &RubikLayerTransform::U2,
+    &RubikLayerTransform::UI,
+    &RubikLayerTransform::D,
+    &RubikLayerTransform::D2,
+    &RubikLayerTransform::DI,
+    &RubikLayerTransform::F,
+    &RubikLayerTransform::F2,
+    &RubikLayerTransform::FI,
+    &RubikLayerTransform::B,
+    &RubikLayerTransform::B2,
+    &RubikLayerTransform::BI,
+    &RubikLayerTransform::L,
+    &RubikLayerTransform::L2,
+    &RubikLayerTransform::LI,
+    &RubikLayerTransform::R,
+    &RubikLayerTransform::R2,
+    &RubikLayerTransform::RI,
+];
+
+/// Iterative deepening depth first search, yielding the solutions of each length in turn.
+pub struct Solutions {
+    root: RubikSolveState,
+    goal: Arc<dyn Goal>,
+    max_len: usize,
+    len: usize,
+    stack: Vec<(usize, RubikSolveState)>,
+}
+
+impl Solutions {
+    /// length of the solutions currently yielded
+    pub fn current_len(&self) -> usize {
+        self.len
+    }
+}
+
+impl Iterator for Solutions {
+    type Item = RubikSolveState;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            while let Some((depth, state)) = self.stack.pop() {
+                if depth == self.len {
+                    if self.goal.is_reached(&state.rubik) {
+                        return Some(state);
+                    }
+                    continue;
+                }
+                // a sequence through the goal only pads a shorter solution
+                if depth + self.goal.dist(&state.rubik) > self.len
+                    || self.goal.is_reached(&state.rubik)
+                {
+                    continue;
+                }
+                let mut next_states = state.canonical_neighbors().collect::<Vec<_>>();
+                next_states.reverse();
+                self.stack
+                    .extend(next_states.into_iter().map(|next| (depth + 1, next)));
+            }
+            if self.len >= self.max_len {
+                return None;
+            }
+            self.len += 1;
+            self.stack.push((0, self.root.clone()));
+        }
+    }
+}
+
+impl RubikMultiSolver for EnumerateSolver {
+    type Solutions = Solutions;
+
+    fn solutions(&self, rubik: Rubik) -> Self::Solutions {
+        let root = RubikSolveState::new(rubik, self.op_set.clone());
+        Solutions {
+            stack: vec![(0, root.clone())],
+            root,
+            goal: self.goal.clone(),
+            max_len: self.max_len,
+            len: 0,
+        }
+    }
+}
+
+impl RubikSolver for EnumerateSolver {
+    /// the first solution of [`RubikMultiSolver::solutions`], or `rubik` as it is if there is
+    /// none within `max_len` moves
+    fn solve(&self, rubik: Rubik) -> RubikSolveState {
+        self.solutions(rubik.clone())
+            .next()
+            .unwrap_or_else(|| RubikSolveState::new(rubik, self.op_set.clone()))
+    }
+}
@@ -0,0 +1,371 @@
+//! Ergonomic scoring of move sequences: how fast a sequence can be played by hand, so that
+//! algorithms of the same length can be told apart.
+//!
+//! Each hand has a wrist, turned by `R` or `L`, that can only go one quarter turn away
+//! from its home position before the hand must regrip. `U` is flicked and `F` pushed with
+//! the index fingers, which requires the wrist of the hand to be on the right side of home,
+//! while `D`, `B` and slices are played from whatever grip. Turns of the whole cube,
+//! played as turns of every layer of an axis, reset the grip.
+use std::sync::OnceLock;
+
+use crate::{
+    cube::CubeFace,
+    prelude::{PtrRotate, RubikLayerTransform as T},
+    Rubik,
+};
+
+use super::steps::quarter_turns;
+
+/// The cost of each kind of move, in rough tenths of a second.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CostTable {
+    /// `R` or `L`, turned with the wrist
+    pub wrist: f64,
+    /// `U`, flicked with an index finger
+    pub flick: f64,
+    /// `F`, pushed with an index finger
+    pub push: f64,
+    /// added to the cost of a quarter turn for a half turn
+    pub half: f64,
+    /// `D` or `B`
+    pub awkward: f64,
+    /// `M`, `E` or `S`
+    pub slice: f64,
+    /// a turn of the whole cube
+    pub rotation: f64,
+    /// bringing a hand back home before its next move
+    pub regrip: f64,
+}
+
+impl Default for CostTable {
+    fn default() -> Self {
+        Self {
+            wrist: 1.0,
+            flick: 0.8,
+            push: 1.2,
+            half: 0.6,
+            awkward: 1.8,
+            slice: 1.4,
+            rotation: 2.5,
+            regrip: 1.5,
+        }
+    }
+}
+
+/// the wrists of the right and left hands, each a quarter turn away from home at most,
+/// positive when turned up as by `R` or `L'`
+#[derive(Debug, Clone, Copy, Default)]
+struct Grip {
+    right: i8,
+    left: i8,
+}
+
+/// Scores move sequences with a [`CostTable`], lower being faster.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Ergonomics {
+    pub costs: CostTable,
+}
+
+impl Ergonomics {
+    pub fn new(costs: CostTable) -> Self {
+        Self { costs }
+    }
+
+    /// the cost of playing `ops` once cancelled, each run of turns about one axis played
+    /// as the cheapest mix of a rotation, wide turns and single layer turns
+    pub fn score(&self, ops: &[&'static T]) -> f64 {
+        let ops = T::cancel(ops.iter().copied());
+        let mut grip = Grip::default();
+        let mut score = 0.0;
+        let mut index = 0;
+        while index < ops.len() {
+            let axis = ops[index].layer().axis();
+            let mut turns = [0_u8; 3];
+            while let Some(op) = ops.get(index).filter(|op| op.layer().axis() == axis) {
+                turns[op.layer().depth() as usize] += quarter_turns(op);
+                index += 1;
+            }
+            let (cost, after) = (0..4)
+                .map(|rotation| {
+                    let mut grip = grip;
+                    let mut cost = 0.0;
+                    if rotation != 0 {
+                        cost += self.costs.rotation;
+                        grip = Grip::default();
+                    }
+                    let mut left = turns.map(|turn| (turn + 4 - rotation % 4) % 4);
+                    // an outer layer and the slice turned together make a wide turn
+                    if left[1] != 0 && (left[0] == left[1] || left[2] == left[1]) {
+                        left[1] = 0;
+                    }
+                    for (depth, turn) in left.into_iter().enumerate() {
+                        if turn != 0 {
+                            cost += self.play(&mut grip, layer_turn(axis, depth as u8, turn));
+                        }
+                    }
+                    (cost, grip)
+                })
+                .min_by(|a, b| a.0.total_cmp(&b.0))
+                .expect("four rotations");
+            score += cost;
+            grip = after;
+        }
+        score
+    }
+
+    /// the cost of `op`, moving the hands
+    fn play(&self, grip: &mut Grip, op: &T) -> f64 {
+        let half = op.ptr_rotate() == PtrRotate::Rotate2;
+        let clockwise = op.ptr_rotate() == PtrRotate::Rotate1;
+        let extra = if half { self.costs.half } else { 0.0 };
+        let regrip = |wrist: &mut i8| {
+            *wrist = 0;
+            self.costs.regrip
+        };
+        let cost = match op.face() {
+            Some(face @ (CubeFace::R | CubeFace::L)) => {
+                let wrist = if face == CubeFace::R {
+                    &mut grip.right
+                } else {
+                    &mut grip.left
+                };
+                let up = (face == CubeFace::R) == clockwise;
+                let mut cost = self.costs.wrist;
+                if half {
+                    if *wrist == 0 {
+                        cost += regrip(wrist);
+                        *wrist = -1;
+                    }
+                    *wrist = -*wrist;
+                } else {
+                    let turn = if up { 1 } else { -1 };
+                    if (*wrist + turn).abs() > 1 {
+                        cost += regrip(wrist);
+                    }
+                    *wrist += turn;
+                }
+                cost
+            }
+            Some(face @ (CubeFace::U | CubeFace::F)) => {
+                // U is flicked from a wrist turned up or home, F from one turned down or home
+                let ready = |wrist: i8| {
+                    if face == CubeFace::U {
+                        wrist >= 0
+                    } else {
+                        wrist <= 0
+                    }
+                };
+                let cost = if face == CubeFace::U {
+                    self.costs.flick
+                } else {
+                    self.costs.push
+                };
+                if half {
+                    if !ready(grip.right) && !ready(grip.left) {
+                        return cost + extra + regrip(&mut grip.right);
+                    }
+                    cost
+                } else {
+                    let wrist = if clockwise {
+                        &mut grip.right
+                    } else {
+                        &mut grip.left
+                    };
+                    if ready(*wrist) {
+                        cost
+                    } else {
+                        cost + regrip(wrist)
+                    }
+                }
+            }
+            Some(_) => self.costs.awkward,
+            None => self.costs.slice,
+        };
+        cost + extra
+    }
+
+    /// `items` from the fastest to the slowest to play, the moves of each given by `moves`
+    pub fn rank<A>(
+        &self,
+        items: impl IntoIterator<Item = A>,
+        moves: impl Fn(&A) -> &[&'static T],
+    ) -> Vec<A> {
+        let mut scored = items
+            .into_iter()
+            .map(|item| (self.score(moves(&item)), item))
+            .collect::<Vec<_>>();
+        scored.sort_by(|a, b| a.0.total_cmp(&b.0));
+        scored.into_iter().map(|(_, item)| item).collect()
+    }
+
+    /// `ops` with a turn of the whole cube inserted where it makes the sequence the
+    /// cheapest, mostly turning `B` and `D` moves into others, the moves after it
+    /// relabelled; no rotation when none helps. The rewritten sequence does the same as
+    /// `ops` followed by the rotation.
+    pub fn reorient(&self, ops: &[&'static T]) -> Reoriented {
+        let ops = T::cancel(ops.iter().copied());
+        let mut best = (
+            self.score(&ops),
+            Reoriented {
+                before: ops.clone(),
+                rotation: None,
+                after: vec![],
+            },
+        );
+        for position in 0..ops.len() {
+            for orientation in &orientations()[1..] {
+                let candidate = Reoriented {
+                    before: ops[..position].to_vec(),
+                    rotation: Some(orientation.rotation.clone()),
+                    after: ops[position..]
+                        .iter()
+                        .map(|op| orientation.moves[move_index(op)])
+                        .collect(),
+                };
+                let score = self.score(&candidate.moves());
+                if score < best.0 {
+                    best = (score, candidate);
+                }
+            }
+        }
+        best.1
+    }
+}
+
+/// A turn of the whole cube, as cubers write it with `x`, `y` and `z`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rotation {
+    /// like `y'` or `x2 z`
+    pub notation: String,
+    /// the turns of every layer of each axis doing the same, as `U E' D'` for `y`
+    pub layers: Vec<&'static T>,
+}
+
+impl std::fmt::Display for Rotation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.notation)
+    }
+}
+
+/// A sequence rewritten by [`Ergonomics::reorient`]: `before`, then the whole cube turned
+/// by `rotation`, then `after`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Reoriented {
+    pub before: Vec<&'static T>,
+    pub rotation: Option<Rotation>,
+    pub after: Vec<&'static T>,
+}
+
+impl Reoriented {
+    /// the moves to play on a [`Rubik`], the rotation as turns of every layer
+    pub fn moves(&self) -> Vec<&'static T> {
+        let rotation = self.rotation.iter().flat_map(|rotation| &rotation.layers);
+        self.before
+            .iter()
+            .chain(rotation)
+            .chain(&self.after)
+            .copied()
+            .collect()
+    }
+}
+
+impl std::fmt::Display for Reoriented {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let rotation = self
+            .rotation
+            .iter()
+            .map(|rotation| rotation.notation.clone());
+        let words = self
+            .before
+            .iter()
+            .map(|op| op.to_string())
+            .chain(rotation)
+            .chain(self.after.iter().map(|op| op.to_string()))
+            .collect::<Vec<_>>();
+        write!(f, "{}", words.join(" "))
+    }
+}
+
+/// the turn of the layer at `depth` on `axis` by `turns` quarter turns of F, L or U
+fn layer_turn(axis: u8, depth: u8, turns: u8) -> &'static T {
+    T::ALL
+        .into_iter()
+        .find(|op| {
+            op.layer().axis() == axis && op.layer().depth() == depth && quarter_turns(op) == turns
+        })
+        .expect("a move of every layer for each turn")
+}
+
+fn move_index(op: &T) -> usize {
+    T::ALL
+        .iter()
+        .position(|other| *other == op)
+        .expect("a move of a single layer")
+}
+
+/// A turn of the whole cube, and how it changes the moves played after it.
+struct Orientation {
+    rotation: Rotation,
+    /// for each move of [`T::ALL`], the one doing the same once the cube is turned
+    moves: Vec<&'static T>,
+}
+
+/// the 24 turns of the whole cube, the identity first, each as the fewest quarter or
+/// half x, y and z rotations
+fn orientations() -> &'static [Orientation] {
+    static ORIENTATIONS: OnceLock<Vec<Orientation>> = OnceLock::new();
+    ORIENTATIONS.get_or_init(|| {
+        let state = |ops: &[&'static T]| {
+            let mut rubik = Rubik::new();
+            ops.iter().for_each(|op| op.apply_on(&mut rubik));
+            rubik
+        };
+        let rotations: [(&str, &[&'static T]); 9] = [
+            ("x", &[&T::R, &T::MI, &T::LI]),
+            ("x'", &[&T::RI, &T::M, &T::L]),
+            ("x2", &[&T::R2, &T::M2, &T::L2]),
+            ("y", &[&T::U, &T::EI, &T::DI]),
+            ("y'", &[&T::UI, &T::E, &T::D]),
+            ("y2", &[&T::U2, &T::E2, &T::D2]),
+            ("z", &[&T::F, &T::SI, &T::BI]),
+            ("z'", &[&T::FI, &T::S, &T::B]),
+            ("z2", &[&T::F2, &T::S2, &T::B2]),
+        ];
+        let mut found: Vec<(Vec<&str>, Vec<&'static T>)> = vec![(vec![], vec![])];
+        let mut index = 0;
+        while index < found.len() {
+            for (name, rotation) in rotations {
+                let (names, ops) = &found[index];
+                let names = names.iter().copied().chain([name]).collect();
+                let ops = T::cancel(ops.iter().chain(rotation).copied());
+                if found.iter().all(|(_, other)| state(other) != state(&ops)) {
+                    found.push((names, ops));
+                }
+            }
+            index += 1;
+        }
+        found
+            .into_iter()
+            .map(|(names, ops)| {
+                let rubik = state(&ops);
+                let moves = T::ALL
+                    .into_iter()
+                    .map(|op| {
+                        let target = state(&[op]).compose(&rubik);
+                        T::ALL
+                            .into_iter()
+                            .find(|turned| rubik.compose(&state(&[*turned])) == target)
+                            .expect("a rotation maps layers to layers")
+                    })
+                    .collect();
+                Orientation {
+                    rotation: Rotation {
+                        notation: names.join(" "),
+                        layers: ops,
+                    },
+                    moves,
+                }
+            })
+            .collect()
+    })
+}
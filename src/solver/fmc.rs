@@ -0,0 +1,422 @@
+//! Fewest moves tools: edge orientation (EO), domino reduction (DR) and half turn
+//! reduction (HTR) on any axis, each enumerating its solutions by increasing length.
+//!
+//! An axis is named by any of its two faces, `CubeFace::F` and `CubeFace::B` both
+//! standing for the F/B axis.
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    sync::{Arc, Mutex, OnceLock},
+};
+
+use crate::{
+    cube::CubeFace,
+    prelude::{PtrRotate, RubikLayerTransform as T},
+    CubePosition, Rubik,
+};
+
+use super::{
+    enumerate::{EnumerateSolver, Solutions, HTM},
+    Goal, RubikMultiSolver, RubikSolveState, RubikSolver,
+};
+
+/// one face of each axis
+pub const AXES: [CubeFace; 3] = [CubeFace::F, CubeFace::R, CubeFace::U];
+
+fn on_axis(face: CubeFace, axis: CubeFace) -> bool {
+    face.axis() == axis.axis()
+}
+
+/// `slot` lies in the middle slice of `axis`
+fn in_slice(slot: CubePosition, axis: CubeFace) -> bool {
+    slot.faces().all(|face| !on_axis(face, axis))
+}
+
+/// the axis neither `a` nor `b`, which must differ
+fn third_axis(a: CubeFace, b: CubeFace) -> CubeFace {
+    debug_assert!(!on_axis(a, b), "{a:?} and {b:?} are on the same axis");
+    AXES.into_iter()
+        .find(|axis| !on_axis(*axis, a) && !on_axis(*axis, b))
+        .expect("three axes")
+}
+
+/// every edge oriented on `axis`
+pub fn is_eo(rubik: &Rubik, axis: CubeFace) -> bool {
+    rubik.bad_edge_count(axis) == 0
+}
+
+/// in the domino group of `axis`, `<U, D, R2, L2, F2, B2>` for the U/D axis:
+/// corners oriented on `axis`, edges oriented on both other axes and the edges of
+/// the middle slice of `axis` inside it
+pub fn is_dr(rubik: &Rubik, axis: CubeFace) -> bool {
+    let edges_oriented = AXES
+        .into_iter()
+        .filter(|other| !on_axis(*other, axis))
+        .all(|other| is_eo(rubik, other));
+    edges_oriented
+        && CubePosition::all().all(|slot| {
+            let cube = &rubik.cubes[slot as usize];
+            if slot.is_corner() {
+                slot.faces()
+                    .find(|face| on_axis(cube.get(*face), axis))
+                    .is_some_and(|face| on_axis(face, axis))
+            } else if slot.is_edge() {
+                in_slice(slot, axis) == in_slice(cube.home(slot), axis)
+            } else {
+                true
+            }
+        })
+}
+
+/// in the half turn group `<U2, D2, R2, L2, F2, B2>`
+pub fn is_htr(rubik: &Rubik) -> bool {
+    let stickers = CubePosition::all().all(|slot| {
+        let cube = &rubik.cubes[slot as usize];
+        slot.faces().all(|face| on_axis(cube.get(face), face))
+    });
+    stickers && half_turn_corners().contains(&corner_key(rubik, CubeFace::U))
+}
+
+/// the 96 arrangements of the corners reached by half turns
+fn half_turn_corners() -> &'static HashSet<u64> {
+    static CORNERS: OnceLock<HashSet<u64>> = OnceLock::new();
+    CORNERS.get_or_init(|| {
+        KeyTable::new(CubeFace::U, corner_key, &htr_moves(), &[])
+            .dist
+            .into_keys()
+            .collect()
+    })
+}
+
+fn face_turns(keep: impl Fn(CubeFace, PtrRotate) -> bool) -> Vec<&'static T> {
+    HTM.into_iter()
+        .filter(|op| keep(op.face().expect("face turn"), op.ptr_rotate()))
+        .collect()
+}
+
+/// every face turn but the quarter turns of `axis`, keeping the edges oriented on `axis`
+pub fn eo_moves(axis: CubeFace) -> Vec<&'static T> {
+    face_turns(|face, rotate| !on_axis(face, axis) || rotate == PtrRotate::Rotate2)
+}
+
+/// the turns of `axis` and the half turns of the other faces, keeping the domino reduction on `axis`
+pub fn dr_moves(axis: CubeFace) -> Vec<&'static T> {
+    face_turns(|face, rotate| on_axis(face, axis) || rotate == PtrRotate::Rotate2)
+}
+
+/// the half turns, keeping the half turn reduction
+pub fn htr_moves() -> Vec<&'static T> {
+    face_turns(|_, rotate| rotate == PtrRotate::Rotate2)
+}
+
+fn eo_key(rubik: &Rubik, axis: CubeFace) -> u64 {
+    rubik
+        .bad_edges(axis)
+        .fold(0, |key, slot| key | 1 << slot as u64)
+}
+
+/// for each corner slot, which of its faces shows the sticker of `axis`
+fn co_key(rubik: &Rubik, axis: CubeFace) -> u64 {
+    CubePosition::all()
+        .filter(|slot| slot.is_corner())
+        .fold(0, |key, slot| {
+            let cube = &rubik.cubes[slot as usize];
+            let twist = slot
+                .faces()
+                .position(|face| on_axis(cube.get(face), axis))
+                .expect("a corner shows every axis") as u64;
+            key << 2 | twist
+        })
+}
+
+/// the edge slots holding an edge of the middle slice of `axis`
+fn slice_key(rubik: &Rubik, axis: CubeFace) -> u64 {
+    CubePosition::all()
+        .filter(|slot| slot.is_edge())
+        .filter(|slot| in_slice(rubik.cubes[*slot as usize].home(*slot), axis))
+        .fold(0, |key, slot| key | 1 << slot as u64)
+}
+
+/// for each edge slot, the axis of the slice its edge belongs to
+fn slices_key(rubik: &Rubik, _: CubeFace) -> u64 {
+    CubePosition::all()
+        .filter(|slot| slot.is_edge())
+        .fold(0, |key, slot| {
+            let home = rubik.cubes[slot as usize].home(slot);
+            let slice = AXES
+                .into_iter()
+                .position(|axis| in_slice(home, axis))
+                .expect("an edge lies in one slice") as u64;
+            key << 2 | slice
+        })
+}
+
+/// the home of the corner in each corner slot
+fn corner_key(rubik: &Rubik, _: CubeFace) -> u64 {
+    CubePosition::all()
+        .filter(|slot| slot.is_corner())
+        .fold(0, |key, slot| {
+            key << 5 | rubik.cubes[slot as usize].home(slot) as u64
+        })
+}
+
+type Key = fn(&Rubik, CubeFace) -> u64;
+
+type KeyTableKey = (CubeFace, Key, Vec<T>, Vec<T>);
+
+/// Distances to the goal for each value of a coordinate `key` that only depends on
+/// itself and the move made, found by a breadth first search from the solved cube.
+struct KeyTable {
+    axis: CubeFace,
+    key: Key,
+    dist: HashMap<u64, u8>,
+}
+
+impl KeyTable {
+    /// `free` moves reach the goal values, at distance 0
+    fn new(axis: CubeFace, key: Key, moves: &[&'static T], free: &[&'static T]) -> Self {
+        let mut dist = HashMap::new();
+        let mut queue = VecDeque::new();
+        let solved = Rubik::new();
+        dist.insert(key(&solved, axis), 0);
+        queue.push_back((solved, 0));
+        let mut goals = vec![];
+        while let Some((rubik, depth)) = queue.pop_front() {
+            for op in free {
+                let mut next = rubik.clone();
+                op.apply_on(&mut next);
+                dist.entry(key(&next, axis)).or_insert_with(|| {
+                    queue.push_back((next, 0));
+                    0
+                });
+            }
+            goals.push((rubik, depth));
+        }
+        queue.extend(goals);
+        while let Some((rubik, depth)) = queue.pop_front() {
+            for op in moves {
+                let mut next = rubik.clone();
+                op.inverse().apply_on(&mut next);
+                dist.entry(key(&next, axis)).or_insert_with(|| {
+                    queue.push_back((next, depth + 1));
+                    depth + 1
+                });
+            }
+        }
+        Self { axis, key, dist }
+    }
+
+    /// the table of `key` with `moves`, searched the first time it is asked for and shared
+    /// afterwards: every solver of an axis needs the same ones
+    fn shared(axis: CubeFace, key: Key, moves: &[&'static T], free: &[&'static T]) -> Arc<Self> {
+        static TABLES: OnceLock<Mutex<HashMap<KeyTableKey, Arc<KeyTable>>>> = OnceLock::new();
+        let tables = TABLES.get_or_init(Default::default);
+        let index = (
+            axis,
+            key,
+            moves.iter().map(|op| **op).collect(),
+            free.iter().map(|op| **op).collect(),
+        );
+        if let Some(table) = tables.lock().expect("not poisoned").get(&index) {
+            return table.clone();
+        }
+        // searched unlocked, so that other tables can be looked up meanwhile
+        let table = Arc::new(Self::new(axis, key, moves, free));
+        tables
+            .lock()
+            .expect("not poisoned")
+            .entry(index)
+            .or_insert(table)
+            .clone()
+    }
+
+    fn dist(&self, rubik: &Rubik) -> usize {
+        self.dist
+            .get(&(self.key)(rubik, self.axis))
+            .copied()
+            .unwrap_or(u8::MAX) as usize
+    }
+}
+
+/// A reduction goal pruned by key tables.
+struct Reduction {
+    axis: CubeFace,
+    check: fn(&Rubik, CubeFace) -> bool,
+    tables: Vec<Arc<KeyTable>>,
+}
+
+impl Goal for Reduction {
+    fn is_reached(&self, rubik: &Rubik) -> bool {
+        (self.check)(rubik, self.axis)
+    }
+
+    fn dist(&self, rubik: &Rubik) -> usize {
+        self.tables
+            .iter()
+            .map(|table| table.dist(rubik))
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+fn enumerate(moves: Vec<&'static T>, max_len: usize, goal: Reduction) -> EnumerateSolver {
+    EnumerateSolver::new(Arc::from(moves), max_len).with_goal(goal)
+}
+
+/// Finds the edge orientations on `axis` in face turns, shortest first.
+#[derive(Debug, Clone, Copy)]
+pub struct EoSolver {
+    pub axis: CubeFace,
+    pub max_len: usize,
+}
+
+impl EoSolver {
+    fn solver(&self) -> EnumerateSolver {
+        let moves = HTM.to_vec();
+        let goal = Reduction {
+            axis: self.axis,
+            check: is_eo,
+            tables: vec![KeyTable::shared(self.axis, eo_key, &moves, &[])],
+        };
+        enumerate(moves, self.max_len, goal)
+    }
+}
+
+/// Finds the domino reductions on `axis` keeping the edges oriented on `eo_axis`, shortest first.
+#[derive(Debug, Clone, Copy)]
+pub struct DrSolver {
+    pub axis: CubeFace,
+    pub eo_axis: CubeFace,
+    pub max_len: usize,
+}
+
+impl DrSolver {
+    fn solver(&self) -> EnumerateSolver {
+        let moves = eo_moves(self.eo_axis);
+        let third = third_axis(self.axis, self.eo_axis);
+        let goal = Reduction {
+            axis: self.axis,
+            check: is_dr,
+            tables: vec![
+                KeyTable::shared(self.axis, co_key, &moves, &[]),
+                KeyTable::shared(self.axis, slice_key, &moves, &[]),
+                KeyTable::shared(third, eo_key, &moves, &[]),
+            ],
+        };
+        enumerate(moves, self.max_len, goal)
+    }
+}
+
+/// Finds the half turn reductions from a domino reduction on `axis`, shortest first.
+#[derive(Debug, Clone, Copy)]
+pub struct HtrSolver {
+    pub axis: CubeFace,
+    pub max_len: usize,
+}
+
+impl HtrSolver {
+    fn solver(&self) -> EnumerateSolver {
+        let moves = dr_moves(self.axis);
+        let goal = Reduction {
+            axis: self.axis,
+            check: |rubik, _| is_htr(rubik),
+            tables: vec![
+                KeyTable::shared(self.axis, corner_key, &moves, &htr_moves()),
+                KeyTable::shared(self.axis, slices_key, &moves, &[]),
+            ],
+        };
+        enumerate(moves, self.max_len, goal)
+    }
+}
+
+macro_rules! reduction_solver {
+    ($($Solver: ident),*) => {
+        $(
+            impl RubikSolver for $Solver {
+                fn solve(&self, rubik: Rubik) -> RubikSolveState {
+                    self.solver().solve(rubik)
+                }
+            }
+
+            impl RubikMultiSolver for $Solver {
+                type Solutions = Solutions;
+
+                fn solutions(&self, rubik: Rubik) -> Self::Solutions {
+                    self.solver().solutions(rubik)
+                }
+            }
+        )*
+    };
+}
+
+reduction_solver!(EoSolver, DrSolver, HtrSolver);
+
+/// The trigger ending a domino reduction on `axis`: the last quarter turn off `axis`,
+/// together with the setup around it when the solution ends like `R U2 R'`.
+pub fn dr_trigger(ops: &[&'static T], axis: CubeFace) -> Option<Vec<&'static T>> {
+    let quarter = |op: &T| {
+        op.face().is_some_and(|face| !on_axis(face, axis)) && op.ptr_rotate() != PtrRotate::Rotate2
+    };
+    match ops {
+        [.., x, y, z]
+            if quarter(x)
+                && y.face().is_some_and(|face| on_axis(face, axis))
+                && **z == x.inverse() =>
+        {
+            Some(vec![*x, *y, *z])
+        }
+        [.., z] if quarter(z) => Some(vec![*z]),
+        _ => None,
+    }
+}
+
+/// The EO, DR and HTR lists of a scramble, ready to be browsed.
+#[derive(Debug, Clone)]
+pub struct Reductions {
+    pub rubik: Rubik,
+    pub max_len: usize,
+}
+
+impl Reductions {
+    pub fn new(rubik: Rubik, max_len: usize) -> Self {
+        Self { rubik, max_len }
+    }
+
+    /// edge orientations on every axis, shortest first on each
+    pub fn eo(&self) -> impl Iterator<Item = (CubeFace, Solutions)> + '_ {
+        AXES.into_iter().map(|axis| {
+            let solver = EoSolver {
+                axis,
+                max_len: self.max_len,
+            };
+            (axis, solver.solutions(self.rubik.clone()))
+        })
+    }
+
+    /// domino reductions continuing `eo`, an edge orientation on `eo_axis`, on both possible axes
+    pub fn dr(
+        &self,
+        eo: &RubikSolveState,
+        eo_axis: CubeFace,
+    ) -> impl Iterator<Item = (CubeFace, Solutions)> + '_ {
+        let rubik = eo.rubik().clone();
+        AXES.into_iter()
+            .filter(move |axis| !on_axis(*axis, eo_axis))
+            .map(move |axis| {
+                let solver = DrSolver {
+                    axis,
+                    eo_axis,
+                    max_len: self.max_len,
+                };
+                (axis, solver.solutions(rubik.clone()))
+            })
+    }
+
+    /// half turn reductions continuing `dr`, a domino reduction on `axis`
+    pub fn htr(&self, dr: &RubikSolveState, axis: CubeFace) -> Solutions {
+        HtrSolver {
+            axis,
+            max_len: self.max_len,
+        }
+        .solutions(dr.rubik().clone())
+    }
+}
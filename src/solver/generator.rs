@@ -0,0 +1,81 @@
+//! Algorithm generation: every algorithm of a move set up to some length reaching a
+//! goal from a case, for when the first solution found is not enough.
+use std::sync::Arc;
+
+use crate::{prelude::RubikLayerTransform as T, Rubik};
+
+use super::{
+    enumerate::EnumerateSolver,
+    steps::{notation, Metric},
+    Goal, RubikMultiSolver,
+};
+
+/// A generated algorithm with its score, lower being better.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GeneratedAlg {
+    pub moves: Vec<&'static T>,
+    pub score: f64,
+}
+
+impl std::fmt::Display for GeneratedAlg {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", notation(self.moves.iter().copied()))
+    }
+}
+
+/// scores an algorithm, lower being better
+type Score = Arc<dyn Fn(&[&'static T]) -> f64 + Send + Sync>;
+
+/// Lists every algorithm of at most `max_len` moves of a move set solving a case, skipping
+/// those trivially equivalent to another one (see [`EnumerateSolver`]).
+///
+/// Algorithms are sorted by length, then by a score defaulting to the quarter turn count.
+pub struct AlgGenerator {
+    solver: EnumerateSolver,
+    score: Score,
+}
+
+impl AlgGenerator {
+    pub fn new(op_set: &[&'static T], max_len: usize) -> Self {
+        Self {
+            solver: EnumerateSolver::new(Arc::from(op_set), max_len),
+            score: Arc::new(|moves| Metric::Qtm.count(moves) as f64),
+        }
+    }
+
+    /// stop on `goal`, e.g. a [`super::mask::GoalMask`], instead of [`Rubik::is_solved`]
+    pub fn with_goal(mut self, goal: impl Goal + 'static) -> Self {
+        self.solver = self.solver.with_goal(goal);
+        self
+    }
+
+    /// rank algorithms of the same length by `score`, lower first, such as
+    /// [`super::ergonomics::Ergonomics::score`]
+    pub fn with_score(
+        mut self,
+        score: impl Fn(&[&'static T]) -> f64 + Send + Sync + 'static,
+    ) -> Self {
+        self.score = Arc::new(score);
+        self
+    }
+
+    /// every algorithm reaching the goal from `case`
+    pub fn generate(&self, case: &Rubik) -> Vec<GeneratedAlg> {
+        let mut algs = self
+            .solver
+            .solutions(case.clone())
+            .map(|state| {
+                let (_, moves) = state.collect();
+                let score = (self.score)(&moves);
+                GeneratedAlg { moves, score }
+            })
+            .collect::<Vec<_>>();
+        algs.sort_by(|a, b| {
+            a.moves
+                .len()
+                .cmp(&b.moves.len())
+                .then(a.score.total_cmp(&b.score))
+        });
+        algs
+    }
+}
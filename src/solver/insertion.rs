@@ -0,0 +1,253 @@
+//! Insertion finding for fewest moves skeletons: the pieces a skeleton leaves unsolved
+//! are split into cycles, and algorithms are inserted between its moves to solve them.
+//!
+//! Only face turns are supported, in the skeleton as in the algorithms, so that the
+//! centers never leave their slots.
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    cube::Cube,
+    prelude::{RubikLayerTransform as T, RubikTransform},
+    CubePosition, Rubik,
+};
+
+use super::{
+    enumerate::HTM,
+    steps::{invert, notation},
+};
+
+/// Pieces cycled among their slots, or a single piece twisted or flipped in place.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cycle {
+    /// each slot holds the piece of the next one, the last one that of the first one
+    pub slots: Vec<CubePosition>,
+    /// the twist of the corners (0..3) or the flip of the edges (0..2) summed along the cycle
+    pub twist: u8,
+}
+
+impl Cycle {
+    pub fn is_corner(&self) -> bool {
+        self.slots[0].is_corner()
+    }
+}
+
+impl std::fmt::Display for Cycle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let kind = if self.is_corner() { "corner" } else { "edge" };
+        let slots = self
+            .slots
+            .iter()
+            .map(|slot| {
+                slot.faces()
+                    .map(|face| format!("{face:?}"))
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>();
+        match (self.slots.len(), self.twist) {
+            (1, _) if self.is_corner() => write!(f, "twisted corner {}", slots[0]),
+            (1, _) => write!(f, "flipped edge {}", slots[0]),
+            (n, 0) => write!(f, "{kind} {n}-cycle {}", slots.join(" -> ")),
+            (n, _) => write!(f, "misoriented {kind} {n}-cycle {}", slots.join(" -> ")),
+        }
+    }
+}
+
+fn state<'a>(ops: impl IntoIterator<Item = &'a &'static T>) -> Rubik {
+    let mut rubik = Rubik::new();
+    ops.into_iter().for_each(|op| op.apply_on(&mut rubik));
+    rubik.normalized()
+}
+
+/// the cycles of the pieces left unsolved on `rubik`, corners first
+pub fn cycles(rubik: &Rubik) -> Vec<Cycle> {
+    let rubik = rubik.normalized();
+    let mut seen = [false; 27];
+    let mut cycles = vec![];
+    let slots = CubePosition::all()
+        .filter(|slot| slot.is_corner())
+        .chain(CubePosition::all().filter(|slot| slot.is_edge()));
+    for start in slots {
+        if seen[start as usize] || rubik.cubes[start as usize] == Cube::new() {
+            continue;
+        }
+        let mut slots = vec![];
+        let mut slot = start;
+        while !seen[slot as usize] {
+            seen[slot as usize] = true;
+            slots.push(slot);
+            slot = rubik.cubes[slot as usize].home(slot);
+        }
+        let modulo = if start.is_corner() { 3 } else { 2 };
+        let twist = slots
+            .iter()
+            .map(|slot| rubik.cubes[*slot as usize].orientation(*slot))
+            .sum::<u8>()
+            % modulo;
+        cycles.push(Cycle { slots, twist });
+    }
+    cycles
+}
+
+/// An algorithm inserted into a skeleton.
+#[derive(Debug, Clone)]
+pub struct Insertion {
+    /// number of skeleton moves played before the algorithm
+    pub position: usize,
+    pub alg: Vec<&'static T>,
+    /// the cycles of the skeleton it solves
+    pub solves: Vec<Cycle>,
+    /// the skeleton with the algorithm inserted, once cancelled
+    pub moves: Vec<&'static T>,
+}
+
+impl Insertion {
+    /// the skeleton moves before and after the algorithm, the algorithm in brackets
+    pub fn notation(&self, skeleton: &[&'static T]) -> String {
+        let (before, after) = skeleton.split_at(self.position);
+        [
+            notation(before.iter().copied()),
+            format!("[{}]", notation(self.alg.iter().copied())),
+            notation(after.iter().copied()),
+        ]
+        .into_iter()
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+    }
+}
+
+impl std::fmt::Display for Insertion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} at {} ({} moves): {}",
+            notation(self.alg.iter().copied()),
+            self.position,
+            self.moves.len(),
+            notation(self.moves.iter().copied())
+        )
+    }
+}
+
+/// Finds where to insert algorithms of a set into a skeleton, looking each needed
+/// effect up among the effects of the algorithms.
+pub struct InsertionFinder {
+    algs: HashMap<Rubik, Vec<Vec<&'static T>>>,
+}
+
+impl InsertionFinder {
+    /// an insertion finder over `algs` and their inverses
+    pub fn new(algs: impl IntoIterator<Item = Vec<&'static T>>) -> Self {
+        let mut table = HashMap::<Rubik, Vec<Vec<&'static T>>>::new();
+        for alg in algs {
+            let inverse = invert(&alg);
+            for alg in [alg, inverse] {
+                let variants = table.entry(state(&alg)).or_default();
+                if !variants.contains(&alg) {
+                    variants.push(alg);
+                }
+            }
+        }
+        Self { algs: table }
+    }
+
+    /// the 8 move commutators `[X Y X', Z]` of face turns cycling three corners, and their inverses
+    pub fn corner_commutators() -> Self {
+        let mut algs = vec![];
+        for x in HTM {
+            for y in HTM.iter().filter(|y| y.layer().axis() != x.layer().axis()) {
+                let x_inverse = T::from_layer(x.layer(), x.ptr_rotate().inverse());
+                let interchange = [x, *y, x_inverse];
+                for z in HTM {
+                    let z_inverse = T::from_layer(z.layer(), z.ptr_rotate().inverse());
+                    let y_inverse = T::from_layer(y.layer(), y.ptr_rotate().inverse());
+                    let undo = [x, y_inverse, x_inverse];
+                    let commutator = interchange
+                        .into_iter()
+                        .chain([z])
+                        .chain(undo)
+                        .chain([z_inverse])
+                        .collect::<Vec<_>>();
+                    if T::cancel(commutator.iter().copied()).len() != 8 {
+                        continue;
+                    }
+                    let pieces = cycles(&state(&commutator));
+                    if let [cycle] = &pieces[..] {
+                        if cycle.is_corner() && cycle.slots.len() == 3 && cycle.twist == 0 {
+                            algs.push(commutator);
+                        }
+                    }
+                }
+            }
+        }
+        Self::new(algs)
+    }
+
+    /// every insertion of a single algorithm into `skeleton` solving at least one of the
+    /// cycles it leaves on `scramble`, those solving the most cycles first and then the
+    /// shortest once cancelled.
+    ///
+    /// Insert again into [`Insertion::moves`] to solve the cycles left.
+    pub fn insertions(&self, scramble: &Rubik, skeleton: &RubikTransform) -> Vec<Insertion> {
+        let skeleton = skeleton.ops();
+        let mut residual = scramble.clone();
+        skeleton.iter().for_each(|op| op.apply_on(&mut residual));
+        let residual = residual.normalized();
+        let cycles = cycles(&residual);
+        // the inverse of each nonempty set of cycles
+        let targets = (1..1_usize << cycles.len())
+            .map(|set| {
+                let solved = (0..cycles.len())
+                    .filter(|i| set >> i & 1 == 1)
+                    .map(|i| cycles[i].clone())
+                    .collect::<Vec<_>>();
+                let mut part = Rubik::new();
+                solved
+                    .iter()
+                    .flat_map(|cycle| &cycle.slots)
+                    .for_each(|slot| {
+                        part.cubes[*slot as usize] = residual.cubes[*slot as usize];
+                    });
+                (solved, part.inverse())
+            })
+            .collect::<Vec<_>>();
+
+        let mut insertions = vec![];
+        for position in 0..=skeleton.len() {
+            // played after the insertion, the rest of the skeleton conjugates its effect
+            let rest = state(&skeleton[position..]);
+            let rest_inverse = rest.inverse();
+            for (solves, target) in &targets {
+                let needed = rest.compose(target).compose(&rest_inverse);
+                let Some(algs) = self.algs.get(&needed) else {
+                    continue;
+                };
+                for alg in algs {
+                    let moves = T::cancel(
+                        skeleton[..position]
+                            .iter()
+                            .chain(alg)
+                            .chain(&skeleton[position..])
+                            .copied(),
+                    );
+                    insertions.push(Insertion {
+                        position,
+                        alg: alg.clone(),
+                        solves: solves.clone(),
+                        moves,
+                    });
+                }
+            }
+        }
+        insertions.sort_by_key(|insertion| {
+            (
+                usize::MAX - insertion.solves.len(),
+                insertion.moves.len(),
+                insertion.position,
+            )
+        });
+        let mut seen = HashSet::new();
+        insertions.retain(|insertion| seen.insert(insertion.moves.clone()));
+        insertions
+    }
+}
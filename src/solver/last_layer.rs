@@ -0,0 +1,774 @@
+//! Last layer algorithm sets and case recognition, with the first two layers
+//! solved on D and the last layer on U.
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::OnceLock,
+};
+
+use crate::{
+    cube::CubeFace,
+    prelude::RubikLayerTransform::{self as T},
+    CubePosition, Rubik,
+};
+
+use super::{
+    mask::GoalMask,
+    steps::{invert, notation, Alg},
+};
+
+#[rustfmt::skip]
+pub const OLL: [Alg; 57] = [
+    Alg::new("OLL 1", &[&T::R, &T::U2, &T::R2, &T::F, &T::R, &T::FI, &T::U2, &T::RI, &T::F, &T::R, &T::FI]),
+    Alg::new("OLL 2", &[&T::F, &T::R, &T::U, &T::RI, &T::UI, &T::SI, &T::R, &T::U, &T::RI, &T::UI, &T::FI, &T::S]),
+    Alg::new("OLL 3", &[&T::F, &T::SI, &T::R, &T::U, &T::RI, &T::UI, &T::FI, &T::S, &T::UI, &T::F, &T::R, &T::U, &T::RI, &T::UI, &T::FI]),
+    Alg::new("OLL 4", &[&T::F, &T::SI, &T::R, &T::U, &T::RI, &T::UI, &T::FI, &T::S, &T::U, &T::F, &T::R, &T::U, &T::RI, &T::UI, &T::FI]),
+    Alg::new("OLL 5", &[&T::RI, &T::M, &T::U2, &T::R, &T::U, &T::RI, &T::U, &T::R, &T::MI]),
+    Alg::new("OLL 6", &[&T::R, &T::MI, &T::U2, &T::RI, &T::UI, &T::R, &T::UI, &T::RI, &T::M]),
+    Alg::new("OLL 7", &[&T::R, &T::MI, &T::U, &T::RI, &T::U, &T::R, &T::U2, &T::RI, &T::M]),
+    Alg::new("OLL 8", &[&T::LI, &T::MI, &T::UI, &T::L, &T::UI, &T::LI, &T::U2, &T::L, &T::M]),
+    Alg::new("OLL 9", &[&T::R, &T::U, &T::RI, &T::UI, &T::RI, &T::F, &T::R2, &T::U, &T::RI, &T::UI, &T::FI]),
+    Alg::new("OLL 10", &[&T::R, &T::U, &T::RI, &T::U, &T::RI, &T::F, &T::R, &T::FI, &T::R, &T::U2, &T::RI]),
+    Alg::new("OLL 11", &[&T::R, &T::MI, &T::U, &T::RI, &T::U, &T::RI, &T::F, &T::R, &T::FI, &T::R, &T::U2, &T::RI, &T::M]),
+    Alg::new("OLL 12", &[&T::MI, &T::RI, &T::UI, &T::R, &T::UI, &T::RI, &T::U2, &T::R, &T::UI, &T::M]),
+    Alg::new("OLL 13", &[&T::F, &T::U, &T::R, &T::UI, &T::R2, &T::FI, &T::R, &T::U, &T::R, &T::UI, &T::RI]),
+    Alg::new("OLL 14", &[&T::RI, &T::F, &T::R, &T::U, &T::RI, &T::FI, &T::R, &T::F, &T::UI, &T::FI]),
+    Alg::new("OLL 15", &[&T::RI, &T::M, &T::UI, &T::MI, &T::UI, &T::R, &T::U, &T::RI, &T::M, &T::U, &T::R, &T::MI]),
+    Alg::new("OLL 16", &[&T::R, &T::MI, &T::U, &T::M, &T::U, &T::RI, &T::UI, &T::R, &T::MI, &T::UI, &T::RI, &T::M]),
+    Alg::new("OLL 17", &[&T::R, &T::U, &T::RI, &T::U, &T::RI, &T::F, &T::R, &T::FI, &T::U2, &T::RI, &T::F, &T::R, &T::FI]),
+    Alg::new("OLL 18", &[&T::R, &T::MI, &T::U, &T::RI, &T::U, &T::R, &T::U2, &T::R2, &T::M2, &T::UI, &T::R, &T::UI, &T::RI, &T::U2, &T::R, &T::MI]),
+    Alg::new("OLL 19", &[&T::M, &T::U, &T::R, &T::U, &T::RI, &T::UI, &T::MI, &T::RI, &T::F, &T::R, &T::FI]),
+    Alg::new("OLL 20", &[&T::R, &T::MI, &T::U, &T::RI, &T::UI, &T::M2, &T::U, &T::R, &T::UI, &T::RI, &T::UI, &T::MI]),
+    Alg::new("OLL 21", &[&T::R, &T::U2, &T::RI, &T::UI, &T::R, &T::U, &T::RI, &T::UI, &T::R, &T::UI, &T::RI]),
+    Alg::new("OLL 22", &[&T::R, &T::U2, &T::R2, &T::UI, &T::R2, &T::UI, &T::R2, &T::U2, &T::R]),
+    Alg::new("OLL 23", &[&T::R2, &T::DI, &T::R, &T::U2, &T::RI, &T::D, &T::R, &T::U2, &T::R]),
+    Alg::new("OLL 24", &[&T::R, &T::MI, &T::U, &T::RI, &T::UI, &T::RI, &T::M, &T::F, &T::R, &T::FI]),
+    Alg::new("OLL 25", &[&T::FI, &T::R, &T::MI, &T::U, &T::RI, &T::UI, &T::RI, &T::M, &T::F, &T::R]),
+    Alg::new("OLL 26", &[&T::R, &T::U2, &T::RI, &T::UI, &T::R, &T::UI, &T::RI]),
+    Alg::new("OLL 27", &[&T::R, &T::U, &T::RI, &T::U, &T::R, &T::U2, &T::RI]),
+    Alg::new("OLL 28", &[&T::R, &T::MI, &T::U, &T::RI, &T::UI, &T::M, &T::U, &T::R, &T::UI, &T::RI]),
+    Alg::new("OLL 29", &[&T::R, &T::U, &T::RI, &T::UI, &T::R, &T::UI, &T::RI, &T::FI, &T::UI, &T::F, &T::R, &T::U, &T::RI]),
+    Alg::new("OLL 30", &[&T::F, &T::RI, &T::F, &T::R2, &T::UI, &T::RI, &T::UI, &T::R, &T::U, &T::RI, &T::F2]),
+    Alg::new("OLL 31", &[&T::RI, &T::UI, &T::F, &T::U, &T::R, &T::UI, &T::RI, &T::FI, &T::R]),
+    Alg::new("OLL 32", &[&T::L, &T::U, &T::FI, &T::UI, &T::LI, &T::U, &T::L, &T::F, &T::LI]),
+    Alg::new("OLL 33", &[&T::R, &T::U, &T::RI, &T::UI, &T::RI, &T::F, &T::R, &T::FI]),
+    Alg::new("OLL 34", &[&T::R, &T::U, &T::R2, &T::UI, &T::RI, &T::F, &T::R, &T::U, &T::R, &T::UI, &T::FI]),
+    Alg::new("OLL 35", &[&T::R, &T::U2, &T::R2, &T::F, &T::R, &T::FI, &T::R, &T::U2, &T::RI]),
+    Alg::new("OLL 36", &[&T::LI, &T::UI, &T::L, &T::UI, &T::LI, &T::U, &T::L, &T::U, &T::L, &T::FI, &T::LI, &T::F]),
+    Alg::new("OLL 37", &[&T::F, &T::RI, &T::FI, &T::R, &T::U, &T::R, &T::UI, &T::RI]),
+    Alg::new("OLL 38", &[&T::R, &T::U, &T::RI, &T::U, &T::R, &T::UI, &T::RI, &T::UI, &T::RI, &T::F, &T::R, &T::FI]),
+    Alg::new("OLL 39", &[&T::L, &T::FI, &T::LI, &T::UI, &T::L, &T::U, &T::F, &T::UI, &T::LI]),
+    Alg::new("OLL 40", &[&T::RI, &T::F, &T::R, &T::U, &T::RI, &T::UI, &T::FI, &T::U, &T::R]),
+    Alg::new("OLL 41", &[&T::R, &T::U, &T::RI, &T::U, &T::R, &T::U2, &T::RI, &T::F, &T::R, &T::U, &T::RI, &T::UI, &T::FI]),
+    Alg::new("OLL 42", &[&T::RI, &T::UI, &T::R, &T::UI, &T::RI, &T::U2, &T::R, &T::F, &T::R, &T::U, &T::RI, &T::UI, &T::FI]),
+    Alg::new("OLL 43", &[&T::FI, &T::UI, &T::LI, &T::U, &T::L, &T::F]),
+    Alg::new("OLL 44", &[&T::F, &T::U, &T::R, &T::UI, &T::RI, &T::FI]),
+    Alg::new("OLL 45", &[&T::F, &T::R, &T::U, &T::RI, &T::UI, &T::FI]),
+    Alg::new("OLL 46", &[&T::RI, &T::UI, &T::RI, &T::F, &T::R, &T::FI, &T::U, &T::R]),
+    Alg::new("OLL 47", &[&T::RI, &T::UI, &T::RI, &T::F, &T::R, &T::FI, &T::RI, &T::F, &T::R, &T::FI, &T::U, &T::R]),
+    Alg::new("OLL 48", &[&T::F, &T::R, &T::U, &T::RI, &T::UI, &T::R, &T::U, &T::RI, &T::UI, &T::FI]),
+    Alg::new("OLL 49", &[&T::R, &T::MI, &T::UI, &T::R2, &T::M2, &T::U, &T::R2, &T::M2, &T::U, &T::R2, &T::M2, &T::UI, &T::R, &T::MI]),
+    Alg::new("OLL 50", &[&T::RI, &T::M, &T::U, &T::R2, &T::M2, &T::UI, &T::R2, &T::M2, &T::UI, &T::R2, &T::M2, &T::U, &T::RI, &T::M]),
+    Alg::new("OLL 51", &[&T::F, &T::U, &T::R, &T::UI, &T::RI, &T::U, &T::R, &T::UI, &T::RI, &T::FI]),
+    Alg::new("OLL 52", &[&T::R, &T::U, &T::RI, &T::U, &T::R, &T::UI, &T::B, &T::UI, &T::BI, &T::RI]),
+    Alg::new("OLL 53", &[&T::LI, &T::MI, &T::U2, &T::L, &T::U, &T::LI, &T::UI, &T::L, &T::U, &T::LI, &T::U, &T::L, &T::M]),
+    Alg::new("OLL 54", &[&T::R, &T::MI, &T::U2, &T::RI, &T::UI, &T::R, &T::U, &T::RI, &T::UI, &T::R, &T::UI, &T::RI, &T::M]),
+    Alg::new("OLL 55", &[&T::R, &T::U2, &T::R2, &T::UI, &T::R, &T::UI, &T::RI, &T::U2, &T::F, &T::R, &T::FI]),
+    Alg::new("OLL 56", &[&T::F, &T::R, &T::U, &T::RI, &T::UI, &T::R, &T::FI, &T::R, &T::MI, &T::U, &T::RI, &T::UI, &T::RI, &T::M]),
+    Alg::new("OLL 57", &[&T::R, &T::U, &T::RI, &T::UI, &T::MI, &T::U, &T::R, &T::UI, &T::RI, &T::M]),
+];
+
+#[rustfmt::skip]
+pub const PLL: [Alg; 21] = [
+    Alg::new("Aa perm", &[&T::RI, &T::F, &T::RI, &T::B2, &T::R, &T::FI, &T::RI, &T::B2, &T::R2]),
+    Alg::new("Ab perm", &[&T::R2, &T::B2, &T::R, &T::F, &T::RI, &T::B2, &T::R, &T::FI, &T::R]),
+    Alg::new("E perm", &[&T::LI, &T::B, &T::L, &T::FI, &T::LI, &T::BI, &T::L, &T::F, &T::LI, &T::BI, &T::L, &T::FI, &T::LI, &T::B, &T::L, &T::F]),
+    Alg::new("F perm", &[&T::RI, &T::UI, &T::FI, &T::R, &T::U, &T::RI, &T::UI, &T::RI, &T::F, &T::R2, &T::UI, &T::RI, &T::UI, &T::R, &T::U, &T::RI, &T::U, &T::R]),
+    Alg::new("Ga perm", &[&T::R2, &T::U, &T::RI, &T::U, &T::RI, &T::UI, &T::R, &T::UI, &T::R2, &T::UI, &T::D, &T::RI, &T::U, &T::R, &T::DI]),
+    Alg::new("Gb perm", &[&T::RI, &T::UI, &T::R, &T::U, &T::DI, &T::R2, &T::U, &T::RI, &T::U, &T::R, &T::UI, &T::R, &T::UI, &T::R2, &T::D]),
+    Alg::new("Gc perm", &[&T::R2, &T::UI, &T::R, &T::UI, &T::R, &T::U, &T::RI, &T::U, &T::R2, &T::U, &T::DI, &T::R, &T::UI, &T::RI, &T::D]),
+    Alg::new("Gd perm", &[&T::R, &T::U, &T::RI, &T::UI, &T::D, &T::R2, &T::UI, &T::R, &T::UI, &T::RI, &T::U, &T::RI, &T::U, &T::R2, &T::DI]),
+    Alg::new("H perm", &[&T::M2, &T::U, &T::M2, &T::U2, &T::M2, &T::U, &T::M2]),
+    Alg::new("Ja perm", &[&T::LI, &T::UI, &T::L, &T::F, &T::LI, &T::UI, &T::L, &T::U, &T::L, &T::FI, &T::L2, &T::U, &T::L]),
+    Alg::new("Jb perm", &[&T::R, &T::U, &T::RI, &T::FI, &T::R, &T::U, &T::RI, &T::UI, &T::RI, &T::F, &T::R2, &T::UI, &T::RI]),
+    Alg::new("Na perm", &[&T::R, &T::U, &T::RI, &T::U, &T::R, &T::U, &T::RI, &T::FI, &T::R, &T::U, &T::RI, &T::UI, &T::RI, &T::F, &T::R2, &T::UI, &T::RI, &T::U2, &T::R, &T::UI, &T::RI]),
+    Alg::new("Nb perm", &[&T::RI, &T::U, &T::R, &T::UI, &T::RI, &T::FI, &T::UI, &T::F, &T::R, &T::U, &T::RI, &T::F, &T::RI, &T::FI, &T::R, &T::UI, &T::R]),
+    Alg::new("Ra perm", &[&T::R, &T::UI, &T::RI, &T::UI, &T::R, &T::U, &T::R, &T::D, &T::RI, &T::UI, &T::R, &T::DI, &T::RI, &T::U2, &T::RI]),
+    Alg::new("Rb perm", &[&T::R2, &T::F, &T::R, &T::U, &T::R, &T::UI, &T::RI, &T::FI, &T::R, &T::U2, &T::RI, &T::U2, &T::R]),
+    Alg::new("T perm", &[&T::R, &T::U, &T::RI, &T::UI, &T::RI, &T::F, &T::R2, &T::UI, &T::RI, &T::UI, &T::R, &T::U, &T::RI, &T::FI]),
+    Alg::new("Ua perm", &[&T::M2, &T::U, &T::M, &T::U2, &T::MI, &T::U, &T::M2]),
+    Alg::new("Ub perm", &[&T::M2, &T::UI, &T::M, &T::U2, &T::MI, &T::UI, &T::M2]),
+    Alg::new("V perm", &[&T::RI, &T::U, &T::RI, &T::UI, &T::R, &T::DI, &T::RI, &T::D, &T::RI, &T::U, &T::DI, &T::R2, &T::UI, &T::R2, &T::D, &T::R2]),
+    Alg::new("Y perm", &[&T::F, &T::R, &T::UI, &T::RI, &T::UI, &T::R, &T::U, &T::RI, &T::FI, &T::R, &T::U, &T::RI, &T::UI, &T::RI, &T::F, &T::R, &T::FI]),
+    Alg::new("Z perm", &[&T::MI, &T::U, &T::M2, &T::U, &T::M2, &T::U, &T::MI, &T::U2, &T::M2]),
+];
+
+/// the four adjustments of the U face, starting with none
+pub const AUF: [&T; 4] = [&T::UNIT, &T::U, &T::U2, &T::UI];
+
+/// A recognized last layer case: `alg` solves it once U is turned by `pre_auf`.
+#[derive(Debug, Clone, Copy)]
+pub struct Case {
+    pub alg: &'static Alg,
+    pub pre_auf: &'static T,
+}
+
+impl Case {
+    /// the pre-AUF followed by the algorithm
+    pub fn ops(&self) -> Vec<&'static T> {
+        (*self.pre_auf != T::UNIT)
+            .then_some(self.pre_auf)
+            .into_iter()
+            .chain(self.alg.moves.iter().copied())
+            .collect()
+    }
+
+    pub fn notation(&self) -> String {
+        notation(self.ops())
+    }
+}
+
+impl std::fmt::Display for Case {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} ({})", self.alg.name, self.notation())
+    }
+}
+
+/// the slots of the last layer
+pub fn last_layer() -> impl Iterator<Item = CubePosition> {
+    CubePosition::all().filter(|p| p.faces().any(|f| f == CubeFace::U))
+}
+
+/// first two layers solved and every last layer piece oriented
+pub fn is_oriented(rubik: &Rubik) -> bool {
+    GoalMask::f2l(CubeFace::D)
+        .orient(last_layer())
+        .is_reached(rubik)
+}
+
+/// solved up to a turn of U
+pub fn is_aligned(rubik: &Rubik) -> bool {
+    AUF.iter().any(|auf| {
+        let mut rubik = rubik.clone();
+        auf.apply_on(&mut rubik);
+        rubik.is_solved()
+    })
+}
+
+/// the first algorithm of `algs` reaching `goal` after some pre-AUF,
+/// `None` if there is none, which includes an already reached `goal`
+pub fn recognize(
+    rubik: &Rubik,
+    algs: &'static [Alg],
+    goal: impl Fn(&Rubik) -> bool,
+) -> Option<Case> {
+    if goal(rubik) {
+        return None;
+    }
+    algs.iter().find_map(|alg| {
+        AUF.iter().find_map(|pre_auf| {
+            let mut rubik = rubik.clone();
+            pre_auf.apply_on(&mut rubik);
+            for op in alg.moves {
+                op.apply_on(&mut rubik);
+            }
+            goal(&rubik).then_some(Case { alg, pre_auf })
+        })
+    })
+}
+
+/// the algorithm of `algs` whose case has the same `key`, a canonical projection of
+/// [`LlState`], as `rubik`, then recognized as [`recognize`] does
+fn recognize_by_key(
+    rubik: &Rubik,
+    table: &HashMap<LlState, &'static Alg>,
+    key: fn(LlState) -> LlState,
+    goal: impl Fn(&Rubik) -> bool,
+) -> Option<Case> {
+    let alg = table.get(&key(LlState::new(rubik)?))?;
+    recognize(rubik, std::slice::from_ref(*alg), goal)
+}
+
+/// the cases of `algs` by their `key`, an algorithm solving the state it is the inverse of
+fn case_table(algs: &'static [Alg], key: fn(LlState) -> LlState) -> HashMap<LlState, &'static Alg> {
+    let mut table = HashMap::new();
+    for alg in algs {
+        let mut rubik = Rubik::new();
+        for op in invert(alg.moves) {
+            op.apply_on(&mut rubik);
+        }
+        let state = LlState::new(&rubik).expect("last layer algorithms keep the first two layers");
+        table.entry(key(state)).or_insert(alg);
+    }
+    table
+}
+
+pub fn recognize_oll(rubik: &Rubik) -> Option<Case> {
+    static CASES: OnceLock<HashMap<LlState, &'static Alg>> = OnceLock::new();
+    let cases = CASES.get_or_init(|| case_table(&OLL, LlState::oll_key));
+    recognize_by_key(rubik, cases, LlState::oll_key, is_oriented)
+}
+
+pub fn recognize_pll(rubik: &Rubik) -> Option<Case> {
+    static CASES: OnceLock<HashMap<LlState, &'static Alg>> = OnceLock::new();
+    let cases = CASES.get_or_init(|| case_table(&PLL, LlState::pll_key));
+    recognize_by_key(rubik, cases, LlState::pll_key, is_aligned)
+}
+
+/// the usual names of the OLL cases with their corners unoriented and their edges oriented
+const OLL_NICKNAMES: [(&str, &str); 7] = [
+    ("OLL 21", "H"),
+    ("OLL 22", "Pi"),
+    ("OLL 23", "Headlights"),
+    ("OLL 24", "Chameleon"),
+    ("OLL 25", "Bowtie"),
+    ("OLL 26", "Antisune"),
+    ("OLL 27", "Sune"),
+];
+
+/// the name of each corner orientation of the COLL and ZBLL sets, after the OLL case
+/// with the same corners and oriented edges
+const CORNER_SETS: [(&str, &str); 7] = [
+    ("OLL 21", "H"),
+    ("OLL 22", "Pi"),
+    ("OLL 23", "U"),
+    ("OLL 24", "T"),
+    ("OLL 25", "L"),
+    ("OLL 26", "AS"),
+    ("OLL 27", "S"),
+];
+
+impl Case {
+    /// the case name as cubers say it, like `OLL 27 / Sune` or `PLL T`
+    pub fn label(&self) -> String {
+        let name = self.alg.name;
+        if let Some(letter) = name.strip_suffix(" perm") {
+            return format!("PLL {letter}");
+        }
+        match OLL_NICKNAMES.iter().find(|(oll, _)| *oll == name) {
+            Some((_, nickname)) => format!("{name} / {nickname}"),
+            None => name.to_string(),
+        }
+    }
+}
+
+/// The last layer as seen with the first two layers solved on D: for each corner and
+/// edge slot of U, in the order a U turn moves them, the index of the slot its piece
+/// belongs to and its orientation as given by [`crate::cube::Cube::orientation`].
+///
+/// Turning U shifts the slots and leaves the orientations alone, so that canonical keys,
+/// the least state among those differing by turns of U before or after, name a case
+/// whatever the angle it is seen from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct LlState {
+    pub corners: [(u8, u8); 4],
+    pub edges: [(u8, u8); 4],
+}
+
+/// the last layer slots of corners or of edges, each one followed by the slot U turns it to
+fn ll_slots(corners: bool) -> [CubePosition; 4] {
+    let mut slot = if corners {
+        CubePosition::FUR
+    } else {
+        CubePosition::FU
+    };
+    [(); 4].map(|_| {
+        let current = slot;
+        slot = T::U.destination(slot);
+        current
+    })
+}
+
+impl LlState {
+    /// `None` unless the first two layers are solved
+    pub fn new(rubik: &Rubik) -> Option<Self> {
+        if !GoalMask::f2l(CubeFace::D).is_reached(rubik) {
+            return None;
+        }
+        let pieces = |corners: bool| {
+            let slots = ll_slots(corners);
+            slots.map(|slot| {
+                let cube = rubik.cubes[slot as usize];
+                let home = cube.home(slot);
+                let index = slots.iter().position(|s| *s == home);
+                (
+                    index.expect("the first two layers are solved") as u8,
+                    cube.orientation(slot),
+                )
+            })
+        };
+        Some(Self {
+            corners: pieces(true),
+            edges: pieces(false),
+        })
+    }
+
+    /// the state after a turn of U
+    pub fn turn(self) -> Self {
+        let turn = |pieces: [(u8, u8); 4]| [0, 1, 2, 3].map(|i| pieces[(i + 3) % 4]);
+        Self {
+            corners: turn(self.corners),
+            edges: turn(self.edges),
+        }
+    }
+
+    /// the state with every piece belonging one slot further, as when a turn of U is
+    /// played before it
+    pub fn relabel(self) -> Self {
+        let relabel = |pieces: [(u8, u8); 4]| pieces.map(|(home, twist)| ((home + 1) % 4, twist));
+        Self {
+            corners: relabel(self.corners),
+            edges: relabel(self.edges),
+        }
+    }
+
+    /// the least of the states differing by turns of U before or after
+    pub fn canonical(self) -> Self {
+        self.canonical_by(|state| state)
+    }
+
+    /// the least `projection` of the states differing by turns of U before or after
+    fn canonical_by(self, projection: fn(Self) -> Self) -> Self {
+        let mut least = projection(self);
+        let mut turned = self;
+        for _ in 0..4 {
+            let mut relabeled = turned;
+            for _ in 0..4 {
+                least = least.min(projection(relabeled));
+                relabeled = relabeled.relabel();
+            }
+            turned = turned.turn();
+        }
+        least
+    }
+
+    /// the orientations alone
+    pub fn orientation(self) -> Self {
+        let forget = |pieces: [(u8, u8); 4]| pieces.map(|(_, twist)| (0, twist));
+        Self {
+            corners: forget(self.corners),
+            edges: forget(self.edges),
+        }
+    }
+
+    /// the homes alone
+    pub fn permutation(self) -> Self {
+        let forget = |pieces: [(u8, u8); 4]| pieces.map(|(home, _)| (home, 0));
+        Self {
+            corners: forget(self.corners),
+            edges: forget(self.edges),
+        }
+    }
+
+    /// the corners alone
+    pub fn corners(self) -> Self {
+        Self {
+            corners: self.corners,
+            edges: [(0, 0); 4],
+        }
+    }
+
+    pub fn edges_oriented(&self) -> bool {
+        self.edges.iter().all(|(_, flip)| *flip == 0)
+    }
+
+    pub fn oll_key(self) -> Self {
+        self.canonical_by(Self::orientation)
+    }
+
+    pub fn pll_key(self) -> Self {
+        self.canonical_by(Self::permutation)
+    }
+
+    pub fn coll_key(self) -> Self {
+        self.canonical_by(Self::corners)
+    }
+
+    pub fn zbll_key(self) -> Self {
+        self.canonical()
+    }
+
+    /// the state mirrored through the plane between L and R, twisting its corners the
+    /// other way
+    pub fn mirror(self) -> Self {
+        let images = |corners: bool| {
+            let slots = ll_slots(corners);
+            slots.map(|slot| {
+                let [x, y, z] = slot.coordinate();
+                let image = CubePosition::from_coordinate([-x, y, z]).expect("a slot");
+                slots.iter().position(|s| *s == image).expect("a slot of U") as u8
+            })
+        };
+        let (corners, edges) = (images(true), images(false));
+        let mut mirrored = self;
+        for i in 0..4 {
+            let (home, twist) = self.corners[i];
+            mirrored.corners[corners[i] as usize] = (corners[home as usize], (3 - twist) % 3);
+            let (home, flip) = self.edges[i];
+            mirrored.edges[edges[i] as usize] = (edges[home as usize], flip);
+        }
+        mirrored
+    }
+
+    /// the state after moves taking a solved last layer to `effect`
+    fn then(self, effect: &Self) -> Self {
+        let then = |pieces: [(u8, u8); 4], effect: [(u8, u8); 4], modulo: u8| {
+            effect.map(|(from, twist)| {
+                let (home, before) = pieces[from as usize];
+                (home, (before + twist) % modulo)
+            })
+        };
+        Self {
+            corners: then(self.corners, effect.corners, 3),
+            edges: then(self.edges, effect.edges, 2),
+        }
+    }
+
+    /// every last layer state reachable with the first two layers solved, in the order
+    /// they are found from the solved state
+    pub fn all() -> impl Iterator<Item = Self> {
+        reachable().states.iter().copied()
+    }
+
+    /// moves taking a solved cube to the state, `None` if it is not reachable
+    pub fn setup(self) -> Option<Vec<&'static T>> {
+        let reachable = reachable();
+        let mut generators = vec![];
+        let mut state = self;
+        while state != reachable.states[0] {
+            let (parent, generator) = reachable.parents.get(&state)?;
+            generators.push(*generator);
+            state = *parent;
+        }
+        Some(T::cancel(generators.into_iter().rev().flat_map(
+            |generator| LL_GENERATORS[generator].iter().copied(),
+        )))
+    }
+}
+
+/// a U turn, Sune, an edge flip and an A perm, keeping the first two layers and
+/// together reaching every last layer state
+#[rustfmt::skip]
+const LL_GENERATORS: [&[&T]; 4] = [
+    &[&T::U],
+    &[&T::R, &T::U, &T::RI, &T::U, &T::R, &T::U2, &T::RI],
+    &[&T::F, &T::R, &T::U, &T::RI, &T::UI, &T::FI],
+    &[&T::RI, &T::F, &T::RI, &T::B2, &T::R, &T::FI, &T::RI, &T::B2, &T::R2],
+];
+
+/// the last layer states found playing [`LL_GENERATORS`] from the solved state, each
+/// with the state it was first reached from and the generator played
+struct Reachable {
+    states: Vec<LlState>,
+    parents: HashMap<LlState, (LlState, usize)>,
+}
+
+fn reachable() -> &'static Reachable {
+    static REACHABLE: OnceLock<Reachable> = OnceLock::new();
+    REACHABLE.get_or_init(|| {
+        let start = LlState::new(&Rubik::new()).expect("the first two layers are solved");
+        // each generator played once on the cube, then composed with the states found
+        let generators = LL_GENERATORS.map(|moves| {
+            let mut rubik = Rubik::new();
+            moves.iter().for_each(|op| op.apply_on(&mut rubik));
+            LlState::new(&rubik).expect("the generators keep the first two layers")
+        });
+        let mut reachable = Reachable {
+            states: vec![start],
+            parents: HashMap::new(),
+        };
+        let mut queue = VecDeque::from([start]);
+        while let Some(state) = queue.pop_front() {
+            for (generator, effect) in generators.iter().enumerate() {
+                let found = state.then(effect);
+                if found != start && !reachable.parents.contains_key(&found) {
+                    reachable.parents.insert(found, (state, generator));
+                    reachable.states.push(found);
+                    queue.push_back(found);
+                }
+            }
+        }
+        reachable
+    })
+}
+
+/// A set of last layer cases, a case being the states one algorithm solves with turns
+/// of U before and after it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LlSet {
+    Oll,
+    Pll,
+    Coll,
+    Zbll,
+    /// the whole last layer in one algorithm
+    OneLook,
+}
+
+/// A case of a last layer set and how often it comes up.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CaseStats {
+    /// the key shared by the states of the case
+    pub key: LlState,
+    /// the first state of the case found by [`LlState::all`]
+    pub example: LlState,
+    /// the number of states of the case among the states the set applies to
+    pub states: usize,
+    /// the chance of getting the case, the states the set applies to being equally likely
+    pub probability: f64,
+}
+
+impl LlSet {
+    pub const ALL: [LlSet; 5] = [
+        LlSet::Oll,
+        LlSet::Pll,
+        LlSet::Coll,
+        LlSet::Zbll,
+        LlSet::OneLook,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            LlSet::Oll => "OLL",
+            LlSet::Pll => "PLL",
+            LlSet::Coll => "COLL",
+            LlSet::Zbll => "ZBLL",
+            LlSet::OneLook => "1LLL",
+        }
+    }
+
+    /// whether the set applies to `state`: PLL once oriented, COLL and ZBLL once the
+    /// edges are oriented and the others always
+    pub fn applies_to(self, state: &LlState) -> bool {
+        match self {
+            LlSet::Oll | LlSet::OneLook => true,
+            LlSet::Pll => {
+                let solved = LlState::new(&Rubik::new()).expect("solved");
+                state.orientation() == solved.orientation()
+            }
+            LlSet::Coll | LlSet::Zbll => state.edges_oriented(),
+        }
+    }
+
+    /// the key of the case of `state`, also shared with the mirrored case when `mirror`
+    pub fn key(self, state: LlState, mirror: bool) -> LlState {
+        let key = match self {
+            LlSet::Oll => LlState::oll_key,
+            LlSet::Pll => LlState::pll_key,
+            LlSet::Coll => LlState::coll_key,
+            LlSet::Zbll | LlSet::OneLook => LlState::zbll_key,
+        };
+        if mirror {
+            key(state).min(key(state.mirror()))
+        } else {
+            key(state)
+        }
+    }
+
+    /// the skip, the case of the solved state, with its chance
+    pub fn skip(self, mirror: bool) -> CaseStats {
+        let solved = LlState::new(&Rubik::new()).expect("solved");
+        self.stats(mirror)
+            .into_iter()
+            .find(|case| case.key == self.key(solved, mirror))
+            .expect("the solved state is reachable")
+    }
+
+    /// every case of the set but the skip, in the order of their keys: 57 for OLL,
+    /// 21 for PLL, 42 for COLL, 493 for ZBLL and 3915 for 1LLL, making 3916 last layer
+    /// positions with the solved one
+    pub fn cases(self, mirror: bool) -> Vec<CaseStats> {
+        let skip = self.key(LlState::new(&Rubik::new()).expect("solved"), mirror);
+        self.stats(mirror)
+            .into_iter()
+            .filter(|case| case.key != skip)
+            .collect()
+    }
+
+    /// every case of the set but the skip with the name [`recognize_ll`] gives it: OLL
+    /// and PLL cases in the order of [`OLL`] and [`PLL`], COLL and ZBLL cases set by set
+    /// in the order of their numbers. The cases of 1LLL have no name.
+    ///
+    /// COLL and ZBLL cases are numbered within their corner set in the order of their
+    /// keys, a numbering of this library only: the example of each case, as drawn by
+    /// [`crate::render::svg::last_layer`], tells which state a name like `COLL S 3`
+    /// stands for.
+    pub fn named_cases(self) -> Vec<(String, CaseStats)> {
+        let position = |algs: &[Alg], alg: &Alg| {
+            algs.iter()
+                .position(|known| known.name == alg.name)
+                .expect("a known algorithm")
+        };
+        let mut named = self
+            .cases(false)
+            .into_iter()
+            .filter_map(|case| {
+                let mut rubik = Rubik::new();
+                case.example
+                    .setup()?
+                    .iter()
+                    .for_each(|op| op.apply_on(&mut rubik));
+                let set = corner_set(case.example);
+                let (name, order) = match self {
+                    LlSet::Oll => {
+                        let oll = recognize_oll(&rubik)?;
+                        (oll.label(), (String::new(), position(&OLL, oll.alg)))
+                    }
+                    LlSet::Pll => {
+                        let pll = recognize_pll(&rubik)?;
+                        (pll.label(), (String::new(), position(&PLL, pll.alg)))
+                    }
+                    LlSet::Coll => {
+                        let number = coll_number(case.example);
+                        (format!("COLL {set} {number}"), (set.to_string(), number))
+                    }
+                    LlSet::Zbll => {
+                        let number = zbll_number(case.example);
+                        (format!("ZBLL {set} {number}"), (set.to_string(), number))
+                    }
+                    LlSet::OneLook => return None,
+                };
+                Some((order, name, case))
+            })
+            .collect::<Vec<_>>();
+        named.sort_by(|(a, ..), (b, ..)| a.cmp(b));
+        named
+            .into_iter()
+            .map(|(_, name, case)| (name, case))
+            .collect()
+    }
+
+    /// every case, the skip included, in the order of their keys
+    fn stats(self, mirror: bool) -> Vec<CaseStats> {
+        let mut cases = HashMap::<LlState, CaseStats>::new();
+        let mut total = 0;
+        for state in LlState::all().filter(|state| self.applies_to(state)) {
+            total += 1;
+            let key = self.key(state, mirror);
+            cases
+                .entry(key)
+                .or_insert(CaseStats {
+                    key,
+                    example: state,
+                    states: 0,
+                    probability: 0.0,
+                })
+                .states += 1;
+        }
+        let mut cases = cases.into_values().collect::<Vec<_>>();
+        cases.sort_by_key(|case| case.key);
+        for case in &mut cases {
+            case.probability = case.states as f64 / total as f64;
+        }
+        cases
+    }
+}
+
+/// the name of the corner orientation of `state` in the COLL and ZBLL sets,
+/// `PLL` when the corners are oriented
+fn corner_set(state: LlState) -> &'static str {
+    static SETS: OnceLock<HashMap<LlState, &'static str>> = OnceLock::new();
+    let sets = SETS.get_or_init(|| {
+        let algs = OLL.iter().filter_map(|alg| {
+            let (_, set) = CORNER_SETS.iter().find(|(oll, _)| *oll == alg.name)?;
+            Some((alg, *set))
+        });
+        algs.map(|(alg, set)| {
+            let table = case_table(std::slice::from_ref(alg), |state| {
+                state.canonical_by(|state| state.corners().orientation())
+            });
+            (*table.keys().next().expect("one case"), set)
+        })
+        .collect()
+    });
+    sets.get(&state.canonical_by(|state| state.corners().orientation()))
+        .copied()
+        .unwrap_or("PLL")
+}
+
+/// the cases with oriented edges by their `key`, numbered from 1 within each corner set
+/// in the order of their keys
+fn numbering(key: fn(LlState) -> LlState) -> HashMap<LlState, usize> {
+    let mut cases = LlState::all()
+        .filter(|state| state.edges_oriented())
+        .map(|state| (corner_set(state), key(state)))
+        .collect::<Vec<_>>();
+    cases.sort();
+    cases.dedup();
+    let mut numbers = HashMap::new();
+    let mut current = None;
+    let mut number = 0;
+    for (set, case) in cases {
+        if current != Some(set) {
+            current = Some(set);
+            number = 0;
+        }
+        number += 1;
+        numbers.insert(case, number);
+    }
+    numbers
+}
+
+fn coll_number(state: LlState) -> usize {
+    static NUMBERS: OnceLock<HashMap<LlState, usize>> = OnceLock::new();
+    NUMBERS.get_or_init(|| numbering(LlState::coll_key))[&state.coll_key()]
+}
+
+fn zbll_number(state: LlState) -> usize {
+    static NUMBERS: OnceLock<HashMap<LlState, usize>> = OnceLock::new();
+    NUMBERS.get_or_init(|| numbering(LlState::zbll_key))[&state.zbll_key()]
+}
+
+/// Every name of a last layer case.
+#[derive(Debug, Clone)]
+pub struct LlCase {
+    pub oll: Option<Case>,
+    /// the PLL case left after [`LlCase::oll`]
+    pub pll: Option<Case>,
+    /// like `COLL S 3` when the edges are oriented, the corner set named after its OLL
+    /// case and the cases of a set numbered as [`LlSet::named_cases`] lists them
+    pub coll: Option<String>,
+    /// like `ZBLL T 12`, numbered as [`LlCase::coll`]
+    pub zbll: Option<String>,
+}
+
+impl std::fmt::Display for LlCase {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let names = [
+            self.oll.map(|case| case.label()),
+            self.pll.map(|case| case.label()),
+            self.coll.clone(),
+            self.zbll.clone(),
+        ];
+        let names = names.into_iter().flatten().collect::<Vec<_>>();
+        if names.is_empty() {
+            write!(f, "solved")
+        } else {
+            write!(f, "{}", names.join(", "))
+        }
+    }
+}
+
+/// names the last layer case of `rubik`, `None` unless the first two layers are solved
+pub fn recognize_ll(rubik: &Rubik) -> Option<LlCase> {
+    let state = LlState::new(rubik)?;
+    let oll = recognize_oll(rubik);
+    let mut oriented = rubik.clone();
+    oll.iter()
+        .flat_map(Case::ops)
+        .for_each(|op| op.apply_on(&mut oriented));
+    let pll = recognize_pll(&oriented);
+    let (coll, zbll) = if state.edges_oriented() && !is_aligned(rubik) {
+        let set = corner_set(state);
+        let coll = (state.coll_key() != LlState::new(&Rubik::new())?.coll_key())
+            .then(|| format!("COLL {set} {}", coll_number(state)));
+        let zbll = format!("ZBLL {set} {}", zbll_number(state));
+        (coll, Some(zbll))
+    } else {
+        (None, None)
+    };
+    Some(LlCase {
+        oll,
+        pll,
+        coll,
+        zbll,
+    })
+}
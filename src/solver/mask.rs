@@ -0,0 +1,462 @@
+use std::{
+    collections::{hash_map::Entry, HashMap, VecDeque},
+    sync::{Arc, Mutex, OnceLock},
+};
+
+use crate::{
+    cube::{Cube, CubeFace},
+    prelude::{CubePermutation, RubikLayerTransform},
+    CubePosition, Rubik,
+};
+
+use super::{
+    enumerate::{EnumerateSolver, Solutions, HTM},
+    Goal, RubikMultiSolver, RubikSolveState, RubikSolver,
+};
+
+/// What has to be done with one piece.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub enum PieceGoal {
+    /// don't care
+    #[default]
+    Ignored,
+    /// the piece can be anywhere but must be oriented, see [`Cube::orientation`]
+    Oriented,
+    /// the piece must be at its home position and oriented
+    Solved,
+}
+
+/// A goal giving a [`PieceGoal`] to every piece, pieces being named by their home position.
+///
+/// Positions are fixed slots rather than relative to the centers, so when solving with
+/// slice moves add the centers that matter to the mask. Centers count as solved in any spin.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct GoalMask {
+    pieces: [PieceGoal; 27],
+}
+
+impl Default for GoalMask {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GoalMask {
+    /// every piece ignored
+    pub const fn new() -> Self {
+        Self {
+            pieces: [PieceGoal::Ignored; 27],
+        }
+    }
+
+    /// every piece solved
+    pub const fn solved() -> Self {
+        Self {
+            pieces: [PieceGoal::Solved; 27],
+        }
+    }
+
+    pub fn set(
+        mut self,
+        goal: PieceGoal,
+        positions: impl IntoIterator<Item = CubePosition>,
+    ) -> Self {
+        for position in positions {
+            self.pieces[position as usize] = goal;
+        }
+        self
+    }
+
+    pub fn solve(self, positions: impl IntoIterator<Item = CubePosition>) -> Self {
+        self.set(PieceGoal::Solved, positions)
+    }
+
+    pub fn orient(self, positions: impl IntoIterator<Item = CubePosition>) -> Self {
+        self.set(PieceGoal::Oriented, positions)
+    }
+
+    pub fn ignore(self, positions: impl IntoIterator<Item = CubePosition>) -> Self {
+        self.set(PieceGoal::Ignored, positions)
+    }
+
+    /// the stronger goal of both masks for every piece
+    pub fn union(mut self, other: &Self) -> Self {
+        for (goal, other) in self.pieces.iter_mut().zip(other.pieces) {
+            *goal = (*goal).max(other);
+        }
+        self
+    }
+
+    pub fn get(&self, position: CubePosition) -> PieceGoal {
+        self.pieces[position as usize]
+    }
+
+    /// pieces with a goal other than [`PieceGoal::Ignored`]
+    pub fn pieces(&self) -> impl Iterator<Item = (CubePosition, PieceGoal)> + '_ {
+        CubePosition::all()
+            .map(|position| (position, self.get(position)))
+            .filter(|(_, goal)| *goal != PieceGoal::Ignored)
+    }
+
+    /// the center of `face` and the four edges around it
+    pub fn cross(face: CubeFace) -> Self {
+        Self::new().solve(
+            CubePosition::all()
+                .filter(|p| !p.is_corner() && p.faces().any(|f| f == face))
+                .filter(|p| p.faces().all(|f| f != face.opposite())),
+        )
+    }
+
+    /// every edge oriented on the F/B axis (see [`crate::cube::Cube::orientation`]),
+    /// with the center of `face` and its two edges on the F/B axis solved, `face` being U, D, L or R
+    pub fn eo_line(face: CubeFace) -> Self {
+        debug_assert!(face.axis() != CubeFace::F.axis(), "no line on F or B");
+        Self::new()
+            .orient(CubePosition::all().filter(|p| p.is_edge()))
+            .solve(CubePosition::all().filter(|p| {
+                !p.is_corner()
+                    && p.faces().any(|f| f == face)
+                    && p.faces().all(|f| f == face || f.axis() == CubeFace::F.axis())
+            }))
+    }
+
+    /// every edge oriented on the F/B axis and the cross on `face`, `face` being U, D, L or R
+    pub fn eo_cross(face: CubeFace) -> Self {
+        debug_assert!(face.axis() != CubeFace::F.axis(), "no EO cross on F or B");
+        Self::cross(face).orient(CubePosition::all().filter(|p| p.is_edge()))
+    }
+
+    /// the corner at `corner` and the edge between its two faces other than `face`, `face` being the cross face
+    pub fn pair(face: CubeFace, corner: CubePosition) -> Self {
+        let edge = CubePosition::from_faces(corner.faces().filter(|f| *f != face));
+        Self::new().solve([corner]).solve(edge)
+    }
+
+    /// the cross on `face` and the pair of `corner`
+    pub fn xcross(face: CubeFace, corner: CubePosition) -> Self {
+        Self::cross(face).union(&Self::pair(face, corner))
+    }
+
+    /// the first two layers, every piece except those on the opposite face of `face`
+    pub fn f2l(face: CubeFace) -> Self {
+        Self::new().solve(
+            CubePosition::all()
+                .filter(|p| *p != CubePosition::C)
+                .filter(|p| p.faces().all(|f| f != face.opposite())),
+        )
+    }
+
+    /// the 2x2x2 block around `corner`: the corner, its three edges and its three centers
+    pub fn block(corner: CubePosition) -> Self {
+        Self::new().solve(
+            CubePosition::all().filter(|p| {
+                *p != CubePosition::C && p.faces().all(|f| corner.faces().any(|c| c == f))
+            }),
+        )
+    }
+
+    pub fn is_reached(&self, rubik: &Rubik) -> bool {
+        let solved = self
+            .pieces()
+            .filter(|(_, goal)| *goal == PieceGoal::Solved)
+            .all(|(position, _)| {
+                let cube = &rubik.cubes[position as usize];
+                if position.is_center() {
+                    cube.home(position) == position
+                } else {
+                    position == CubePosition::C || cube.rotation == CubePermutation::UNIT
+                }
+            });
+        let oriented = || {
+            CubePosition::all().all(|slot| {
+                let cube = &rubik.cubes[slot as usize];
+                self.get(cube.home(slot)) != PieceGoal::Oriented || cube.orientation(slot) == 0
+            })
+        };
+        solved && (self.pieces().all(|(_, goal)| goal != PieceGoal::Oriented) || oriented())
+    }
+
+    /// the mask with pruning tables for searching with `op_set`
+    pub fn pruned(&self, op_set: &[&RubikLayerTransform]) -> PrunedMask {
+        let mut moves = op_set.iter().map(|op| op.inverse()).collect::<Vec<_>>();
+        moves.dedup();
+        let mut patterns = vec![];
+
+        let mut exact = self
+            .pieces()
+            .filter(|(p, goal)| *goal == PieceGoal::Solved && *p != CubePosition::C)
+            .map(|(p, _)| p)
+            .collect::<Vec<_>>();
+        exact.sort_by_key(|p| (p.is_center(), p.is_corner()));
+        let mut group = vec![];
+        let mut size = 1;
+        for piece in exact {
+            let factor = match piece.faces().count() {
+                1 => 6,
+                _ => 24,
+            };
+            if size * factor > PATTERN_SIZE || group.len() == PATTERN_PIECES {
+                let group = std::mem::take(&mut group);
+                patterns.push(Pattern::shared(group, false, &moves, |pieces| {
+                    Pattern::exact(pieces, &moves)
+                }));
+                size = 1;
+            }
+            size *= factor;
+            group.push(piece);
+        }
+        if !group.is_empty() {
+            patterns.push(Pattern::shared(group, false, &moves, |pieces| {
+                Pattern::exact(pieces, &moves)
+            }));
+        }
+
+        for kind in [CubePosition::is_edge, CubePosition::is_corner] {
+            let oriented = self
+                .pieces()
+                .filter(|(p, goal)| *goal == PieceGoal::Oriented && kind(*p))
+                .map(|(p, _)| p)
+                .collect::<Vec<_>>();
+            if !oriented.is_empty() {
+                patterns.push(Pattern::shared(oriented, true, &moves, |pieces| {
+                    Pattern::oriented(pieces, kind, &moves)
+                }));
+            }
+        }
+        PrunedMask {
+            mask: self.clone(),
+            patterns,
+        }
+    }
+}
+
+impl Goal for GoalMask {
+    fn is_reached(&self, rubik: &Rubik) -> bool {
+        GoalMask::is_reached(self, rubik)
+    }
+}
+
+/// states a pattern table may hold
+const PATTERN_SIZE: usize = 300_000;
+/// pieces a pattern may track, each taking 16 bits of its `u64` keys
+const PATTERN_PIECES: usize = 4;
+
+/// A pruning table over a few pieces of a mask, holding how many moves at least
+/// are needed to bring those pieces to their goal from any arrangement of them.
+struct Pattern {
+    pieces: Vec<CubePosition>,
+    /// only the slot and orientation of the pieces matter, not which piece is where
+    oriented: bool,
+    dist: HashMap<u64, u8>,
+}
+
+type PieceState = (CubePosition, CubePermutation);
+
+/// the pieces of a pattern, whether it is oriented and the moves it is searched with
+type PatternKey = (Vec<CubePosition>, bool, Vec<RubikLayerTransform>);
+
+impl Pattern {
+    /// the pattern of `pieces` with `moves`, built by `build` the first time it is asked
+    /// for and shared afterwards: masks overlap a lot, as the cross of every F2L pair
+    fn shared(
+        pieces: Vec<CubePosition>,
+        oriented: bool,
+        moves: &[RubikLayerTransform],
+        build: impl FnOnce(Vec<CubePosition>) -> Self,
+    ) -> Arc<Self> {
+        static PATTERNS: OnceLock<Mutex<HashMap<PatternKey, Arc<Pattern>>>> = OnceLock::new();
+        let patterns = PATTERNS.get_or_init(Default::default);
+        let key = (pieces, oriented, moves.to_vec());
+        if let Some(pattern) = patterns.lock().expect("not poisoned").get(&key) {
+            return pattern.clone();
+        }
+        // built unlocked, so that other patterns can be looked up meanwhile
+        let pattern = Arc::new(build(key.0.clone()));
+        patterns
+            .lock()
+            .expect("not poisoned")
+            .entry(key)
+            .or_insert(pattern)
+            .clone()
+    }
+
+    fn exact(pieces: Vec<CubePosition>, moves: &[RubikLayerTransform]) -> Self {
+        let start = pieces.iter().map(|p| (*p, CubePermutation::UNIT)).collect();
+        Self::bfs(pieces, false, vec![start], moves)
+    }
+
+    fn oriented(
+        pieces: Vec<CubePosition>,
+        kind: fn(CubePosition) -> bool,
+        moves: &[RubikLayerTransform],
+    ) -> Self {
+        // the goal is every arrangement of the pieces in slots of their kind, oriented
+        let slots = CubePosition::all().filter(|p| kind(*p)).collect::<Vec<_>>();
+        let mut start = vec![];
+        let mut chosen = vec![];
+        fn choose(
+            slots: &[CubePosition],
+            k: usize,
+            chosen: &mut Vec<PieceState>,
+            start: &mut Vec<Vec<PieceState>>,
+        ) {
+            if chosen.len() == k {
+                start.push(chosen.clone());
+                return;
+            }
+            for (i, slot) in slots.iter().enumerate() {
+                chosen.push((*slot, CubePermutation::UNIT));
+                choose(&slots[i + 1..], k, chosen, start);
+                chosen.pop();
+            }
+        }
+        choose(&slots, pieces.len(), &mut chosen, &mut start);
+        Self::bfs(pieces, true, start, moves)
+    }
+
+    fn bfs(
+        pieces: Vec<CubePosition>,
+        oriented: bool,
+        start: Vec<Vec<PieceState>>,
+        moves: &[RubikLayerTransform],
+    ) -> Self {
+        let mut pattern = Self {
+            pieces,
+            oriented,
+            dist: HashMap::new(),
+        };
+        let mut quene = VecDeque::new();
+        for state in start {
+            pattern.dist.insert(pattern.key(&state), 0);
+            quene.push_back((state, 0));
+        }
+        while let Some((state, depth)) = quene.pop_front() {
+            for op in moves {
+                let next = state
+                    .iter()
+                    .map(|(slot, rotation)| {
+                        if op.layer().contains(&(*slot as u8)) {
+                            (op.destination(*slot), rotation.compose(op.rotation()))
+                        } else {
+                            (*slot, *rotation)
+                        }
+                    })
+                    .collect::<Vec<_>>();
+                let key = pattern.key(&next);
+                if let Entry::Vacant(entry) = pattern.dist.entry(key) {
+                    entry.insert(depth + 1);
+                    quene.push_back((next, depth + 1));
+                }
+            }
+        }
+        pattern
+    }
+
+    fn key(&self, state: &[PieceState]) -> u64 {
+        if self.oriented {
+            state.iter().fold(0, |key, (slot, rotation)| {
+                let orientation = Cube::new_with_rotation(*rotation).orientation(*slot);
+                key | ((1 + orientation as u64) << (2 * *slot as u64))
+            })
+        } else {
+            state.iter().fold(0, |key, (slot, rotation)| {
+                let rotation = if slot.is_center() {
+                    0
+                } else {
+                    rotation.into_inner()
+                };
+                (key << 16) | ((*slot as u64) << 8) | rotation as u64
+            })
+        }
+    }
+
+    fn dist(&self, rubik: &Rubik) -> usize {
+        let mut found = [None; 27];
+        for slot in CubePosition::all() {
+            let cube = &rubik.cubes[slot as usize];
+            found[cube.home(slot) as usize] = Some((slot, cube.rotation));
+        }
+        let state = if self.oriented {
+            found
+                .into_iter()
+                .enumerate()
+                .filter(|(home, _)| self.pieces.iter().any(|p| *p as usize == *home))
+                .filter_map(|(_, state)| state)
+                .collect::<Vec<_>>()
+        } else {
+            self.pieces
+                .iter()
+                .map(|piece| found[*piece as usize].expect("every piece is somewhere"))
+                .collect::<Vec<_>>()
+        };
+        self.dist.get(&self.key(&state)).copied().unwrap_or(u8::MAX) as usize
+    }
+}
+
+/// A [`GoalMask`] with pruning tables, see [`GoalMask::pruned`].
+pub struct PrunedMask {
+    mask: GoalMask,
+    patterns: Vec<Arc<Pattern>>,
+}
+
+impl PrunedMask {
+    pub fn mask(&self) -> &GoalMask {
+        &self.mask
+    }
+}
+
+impl Goal for PrunedMask {
+    fn is_reached(&self, rubik: &Rubik) -> bool {
+        self.mask.is_reached(rubik)
+    }
+
+    fn dist(&self, rubik: &Rubik) -> usize {
+        self.patterns
+            .iter()
+            .map(|pattern| pattern.dist(rubik))
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+/// Finds optimal solutions of a [`GoalMask`], e.g. an optimal cross or 2x2x2 block.
+pub struct MaskSolver {
+    solver: EnumerateSolver,
+}
+
+impl MaskSolver {
+    pub fn new(
+        mask: &GoalMask,
+        op_set: Arc<[&'static RubikLayerTransform]>,
+        max_len: usize,
+    ) -> Self {
+        let goal = mask.pruned(&op_set);
+        Self {
+            solver: EnumerateSolver::new(op_set, max_len).with_goal(goal),
+        }
+    }
+
+    /// with all quarter and half turns of the six faces
+    pub fn htm(mask: &GoalMask) -> Self {
+        Self::new(mask, Arc::new(HTM), 20)
+    }
+
+    /// search solutions of at most `max_len` moves from now on
+    pub fn set_max_len(&mut self, max_len: usize) {
+        self.solver.max_len = max_len;
+    }
+}
+
+impl RubikSolver for MaskSolver {
+    fn solve(&self, rubik: Rubik) -> RubikSolveState {
+        self.solver.solve(rubik)
+    }
+}
+
+impl RubikMultiSolver for MaskSolver {
+    type Solutions = Solutions;
+
+    fn solutions(&self, rubik: Rubik) -> Self::Solutions {
+        self.solver.solutions(rubik)
+    }
+}
@@ -1,11 +1,36 @@
-use std::{rc::Rc, sync::Arc, vec::IntoIter};
+use std::{sync::Arc, vec::IntoIter};
 
-use crate::{prelude::RubikLayerTransform, Rubik};
+use crate::{
+    prelude::{PtrRotate, RubikLayerTransform},
+    Rubik,
+};
 
 pub trait RubikSolver {
     fn solve(&self, rubik: Rubik) -> RubikSolveState;
 }
 
+/// A target state, or set of states, a solver can search for.
+pub trait Goal {
+    fn is_reached(&self, rubik: &Rubik) -> bool;
+    /// a lower bound of the moves left to reach the goal, used for pruning,
+    /// so it must never overestimate
+    fn dist(&self, _rubik: &Rubik) -> usize {
+        0
+    }
+}
+
+impl Goal for fn(&Rubik) -> bool {
+    fn is_reached(&self, rubik: &Rubik) -> bool {
+        self(rubik)
+    }
+}
+
+/// A solver yielding many solutions instead of a single one.
+pub trait RubikMultiSolver {
+    type Solutions: Iterator<Item = RubikSolveState>;
+    fn solutions(&self, rubik: Rubik) -> Self::Solutions;
+}
+
 pub trait TransferableState {
     type Iter: Iterator<Item = Self>;
     fn neighbors(self) -> Self::Iter;
@@ -35,33 +60,78 @@ impl RubikSolveState {
             from: None,
         }
     }
-    pub fn collect(self) -> (Rubik, Vec<&'static RubikLayerTransform>) {
-        let rubik = self.rubik;
-        let mut from = self.from;
-        let mut ops = vec![];
-        while let Some((source, op)) = from {
-            ops.push(op);
-            from = source.from.clone();
+    /// the state after applying `op`
+    pub fn transfer(self, op: &'static RubikLayerTransform) -> Self {
+        let mut rubik = self.rubik.clone();
+        op.apply_on(&mut rubik);
+        Self {
+            rubik,
+            op_set: self.op_set.clone(),
+            from: Some((Arc::new(self), op)),
         }
-        ops.reverse();
-        (rubik, ops)
     }
-}
-
-impl TransferableState for RubikSolveState {
-    type Iter = std::vec::IntoIter<RubikSolveState>;
-
-    fn neighbors(self) -> Self::Iter {
+    pub fn rubik(&self) -> &Rubik {
+        &self.rubik
+    }
+    /// the move leading to this state
+    pub fn last_op(&self) -> Option<&'static RubikLayerTransform> {
+        self.from.as_ref().map(|(_, op)| *op)
+    }
+    /// number of moves from the initial state
+    pub fn depth(&self) -> usize {
+        let mut depth = 0;
+        let mut from = &self.from;
+        while let Some((source, _)) = from {
+            depth += 1;
+            from = &source.from;
+        }
+        depth
+    }
+    /// `op` directly undoes the move leading to this state
+    pub fn is_redundant(&self, op: &RubikLayerTransform) -> bool {
+        self.last_op().is_some_and(|prev| prev.eq(&op.inverse()))
+    }
+    /// `op` makes the move sequence trivially equivalent to another one, i.e. either
+    /// - it turns the same layer as the previous move and both could be done as a single move of the op set,
+    /// - or it turns a layer parallel to the previous move, which commute, but out of the F/L/U to B/R/D order.
+    ///
+    /// Turning the same layer twice is only kept when the op set lacks the combined move,
+    /// and then only as two clockwise turns.
+    pub fn is_trivially_equivalent(&self, op: &RubikLayerTransform) -> bool {
+        let Some((source, prev)) = &self.from else {
+            return false;
+        };
+        if let Some(merged) = prev.merge(*op) {
+            if merged.ptr_rotate() == PtrRotate::Rotate0
+                || self.op_set.iter().any(|m| **m == merged)
+            {
+                return true;
+            }
+            let twice = op == *prev && op.ptr_rotate() == PtrRotate::Rotate1;
+            let thrice = source
+                .last_op()
+                .is_some_and(|prev| prev.layer() == op.layer());
+            return !twice || thrice;
+        }
+        prev.layer().axis() == op.layer().axis() && prev.layer().depth() > op.layer().depth()
+    }
+    /// neighbors skipping every move [`RubikSolveState::is_trivially_equivalent`],
+    /// so that every sequence reached is the canonical one of its class
+    pub fn canonical_neighbors(self) -> IntoIter<Self> {
+        self.neighbors_by(Self::is_trivially_equivalent)
+    }
+    fn neighbors_by(
+        self,
+        skip: impl Fn(&Self, &RubikLayerTransform) -> bool,
+    ) -> IntoIter<Self> {
         let arc_self = Arc::new(self);
         arc_self
             .clone()
             .op_set
             .iter()
             .filter_map(move |op| {
-                if let Some((_, prev_op)) = &arc_self.from {
-                    if (*prev_op).eq(&op.inverse()) {
-                        return None;
-                    }
+                if skip(&arc_self, op) {
+                    return None;
                 }
                 let mut rubik = arc_self.rubik.clone();
                 op.apply_on(&mut rubik);
@@ -74,6 +144,25 @@ impl TransferableState for RubikSolveState {
             .collect::<Vec<_>>()
             .into_iter()
     }
+    pub fn collect(self) -> (Rubik, Vec<&'static RubikLayerTransform>) {
+        let rubik = self.rubik;
+        let mut from = self.from;
+        let mut ops = vec![];
+        while let Some((source, op)) = from {
+            ops.push(op);
+            from = source.from.clone();
+        }
+        ops.reverse();
+        (rubik, ops)
+    }
+}
+
+impl TransferableState for RubikSolveState {
+    type Iter = std::vec::IntoIter<RubikSolveState>;
+
+    fn neighbors(self) -> Self::Iter {
+        self.neighbors_by(Self::is_redundant)
+    }
 
     fn random_transfer(self) -> Self
     where
@@ -90,7 +179,22 @@ impl TransferableState for RubikSolveState {
         }
     }
 }
+pub mod beginner;
+pub mod cfop;
+pub mod commutator;
+pub mod enumerate;
+pub mod ergonomics;
+pub mod fmc;
+pub mod generator;
 pub mod ida_star;
+pub mod insertion;
+pub mod last_layer;
+pub mod mask;
+pub mod niss;
+pub mod one_look;
+pub mod roux;
 pub mod sa;
 pub mod shuffle;
+pub mod steps;
 pub mod thistlethwaite;
+pub mod zz;
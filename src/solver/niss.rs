@@ -0,0 +1,101 @@
+//! NISS (normal/inverse scramble switch) sessions for fewest moves solving: moves are
+//! found on the scramble or on its inverse, and joined into one solution at the end.
+use crate::{
+    prelude::{RubikLayerTransform as T, RubikTransform},
+    Rubik,
+};
+
+use super::steps::{invert, notation};
+
+/// the side of the scramble moves are played on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Side {
+    Normal,
+    Inverse,
+}
+
+/// A fewest moves attempt with moves on both sides of the scramble.
+///
+/// Each side shows the moves of the other side as premoves, so that both always reflect
+/// all the progress made: the normal side is the inverted inverse moves, the scramble then
+/// the normal moves, and the inverse side is its exact inverse.
+#[derive(Debug, Clone)]
+pub struct NissSession {
+    scramble: Rubik,
+    normal: Vec<&'static T>,
+    inverse: Vec<&'static T>,
+}
+
+fn state(ops: &[&'static T]) -> Rubik {
+    let mut rubik = Rubik::new();
+    ops.iter().for_each(|op| op.apply_on(&mut rubik));
+    rubik
+}
+
+impl NissSession {
+    pub fn new(scramble: Rubik) -> Self {
+        Self {
+            scramble,
+            normal: vec![],
+            inverse: vec![],
+        }
+    }
+
+    /// plays `transform` after the moves already on `side`
+    pub fn play(&mut self, side: Side, transform: &RubikTransform) -> &mut Self {
+        let moves = match side {
+            Side::Normal => &mut self.normal,
+            Side::Inverse => &mut self.inverse,
+        };
+        moves.extend(transform.ops());
+        self
+    }
+
+    /// takes back the last move of `side`, if any
+    pub fn undo(&mut self, side: Side) -> Option<&'static T> {
+        match side {
+            Side::Normal => self.normal.pop(),
+            Side::Inverse => self.inverse.pop(),
+        }
+    }
+
+    pub fn moves(&self, side: Side) -> &[&'static T] {
+        match side {
+            Side::Normal => &self.normal,
+            Side::Inverse => &self.inverse,
+        }
+    }
+
+    /// the cube as seen from `side`
+    pub fn state(&self, side: Side) -> Rubik {
+        let normal = state(&invert(&self.inverse))
+            .compose(&self.scramble)
+            .compose(&state(&self.normal));
+        match side {
+            Side::Normal => normal,
+            Side::Inverse => normal.inverse(),
+        }
+    }
+
+    /// the normal moves then the inverse moves inverted, cancelled
+    pub fn solution(&self) -> Vec<&'static T> {
+        T::cancel(self.normal.iter().copied().chain(invert(&self.inverse)))
+    }
+
+    pub fn is_solved(&self) -> bool {
+        self.state(Side::Normal).is_solved()
+    }
+}
+
+/// the normal moves, then the inverse moves in parentheses, as `F R' (U D2)`
+impl std::fmt::Display for NissSession {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let normal = notation(self.normal.iter().copied());
+        let inverse = notation(self.inverse.iter().copied());
+        match (normal.is_empty(), inverse.is_empty()) {
+            (_, true) => write!(f, "{normal}"),
+            (true, false) => write!(f, "({inverse})"),
+            (false, false) => write!(f, "{normal} ({inverse})"),
+        }
+    }
+}
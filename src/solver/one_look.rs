@@ -0,0 +1,604 @@
+//! One-look last layer: the shortest algorithm for every last layer state, as a
+//! reference to judge human last layer solutions against.
+//!
+//! The algorithms of every case with every face turn come with the library under the
+//! `one-look-table` feature, on by default, found once by [`OneLookSolver::precompute`]
+//! and saved as a table file, so that solving is only looking them up. The
+//! `gen_one_look` example writes that file again, built without the feature so that no
+//! case is known beforehand. With `<R, U>` or `<R, U, F>`, cases are searched with IDA*, pruned
+//! by pattern tables of the distance from the corners and from two groups of six edges
+//! to a solved cube with the moves allowed. Algorithms found can be kept in a
+//! [`TableCache`] along with the pattern tables, so that a case is only ever searched
+//! once, and give those of the cases symmetric to it.
+mod pruning;
+
+use std::{
+    collections::HashMap,
+    error::Error,
+    fmt::Display,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex, OnceLock,
+    },
+};
+
+use crate::{
+    cube::CubeFace,
+    prelude::RubikLayerTransform as T,
+    tables::{Table, TableCache, TableError, TableSpec},
+    Rubik,
+};
+
+use self::pruning::{htm_index, Coords, Pruning};
+use super::{
+    enumerate::HTM,
+    last_layer::{LlSet, LlState, AUF},
+    steps::{invert, notation},
+};
+
+/// The moves algorithms are made of.
+///
+/// The algorithms of every face turn come with the library, see the module documentation.
+/// With `<R, U>` or `<R, U, F>`, [`OneLookSolver::solve`] answers
+/// [`LlSolveError::NotSearched`] until [`OneLookSolver::search`] or
+/// [`OneLookSolver::precompute`] searched the case, which first generates about 90 MB of
+/// pattern tables, a precompute then searching every case on every core.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LlMoves {
+    /// every face turn
+    Htm,
+    /// `<R, U>`
+    Ru,
+    /// `<R, U, F>`
+    Ruf,
+}
+
+impl LlMoves {
+    pub fn moves(self) -> &'static [&'static T] {
+        const RU: [&T; 6] = [&T::R, &T::R2, &T::RI, &T::U, &T::U2, &T::UI];
+        const RUF: [&T; 9] = [
+            &T::R,
+            &T::R2,
+            &T::RI,
+            &T::U,
+            &T::U2,
+            &T::UI,
+            &T::F,
+            &T::F2,
+            &T::FI,
+        ];
+        match self {
+            LlMoves::Htm => &HTM,
+            LlMoves::Ru => &RU,
+            LlMoves::Ruf => &RUF,
+        }
+    }
+
+    /// the post-AUFs to search with: turning the whole cube around U makes any
+    /// post-AUF a pre-AUF when every face can be turned
+    fn post_aufs(self) -> &'static [&'static T] {
+        match self {
+            LlMoves::Htm => &[&T::UNIT],
+            LlMoves::Ru | LlMoves::Ruf => &AUF,
+        }
+    }
+}
+
+/// A last layer solution: `alg` once U is turned by `pre_auf`, then `post_auf`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LlSolution {
+    pub pre_auf: &'static T,
+    pub alg: Vec<&'static T>,
+    pub post_auf: &'static T,
+}
+
+impl LlSolution {
+    /// the pre-AUF, the algorithm and the post-AUF
+    pub fn moves(&self) -> Vec<&'static T> {
+        let auf = |auf: &'static T| (*auf != T::UNIT).then_some(auf);
+        auf(self.pre_auf)
+            .into_iter()
+            .chain(self.alg.iter().copied())
+            .chain(auf(self.post_auf))
+            .collect()
+    }
+}
+
+impl std::fmt::Display for LlSolution {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", notation(self.moves()))
+    }
+}
+
+/// an algorithm with the number of turns of U before it
+type TurnedAlg = (usize, Vec<&'static T>);
+
+/// The algorithms found with every face turn, saved as the canonical state, the turn of
+/// U and the number of moves, one byte each, then the index of each move in [`HTM`].
+struct HtmAlgs;
+/// the algorithms found with `<R, U>`, saved as [`HtmAlgs`]
+struct RuAlgs;
+/// the algorithms found with `<R, U, F>`, saved as [`HtmAlgs`]
+struct RufAlgs;
+
+impl TableSpec for HtmAlgs {
+    const NAME: &'static str = "one_look_htm";
+    const VERSION: u32 = 1;
+    /// no algorithm is known until searched for
+    fn generate() -> Vec<u8> {
+        vec![]
+    }
+}
+
+impl TableSpec for RuAlgs {
+    const NAME: &'static str = "one_look_ru";
+    const VERSION: u32 = 1;
+    fn generate() -> Vec<u8> {
+        vec![]
+    }
+}
+
+impl TableSpec for RufAlgs {
+    const NAME: &'static str = "one_look_ruf";
+    const VERSION: u32 = 1;
+    fn generate() -> Vec<u8> {
+        vec![]
+    }
+}
+
+/// the file [`OneLookSolver::save`] wrote after searching every case with every face turn
+#[cfg(feature = "one-look-table")]
+const HTM_ALGS: &[u8] = include_bytes!("one_look/one_look_htm.tbl");
+
+/// the algorithms of `file`, checked against the version and the checksum of its header
+#[cfg(feature = "one-look-table")]
+fn checked<S: TableSpec>(file: &[u8]) -> Result<&[u8], TableError> {
+    use crate::tables::{checksum, TableHeader, HEADER_LEN};
+
+    let header = TableHeader::from_bytes(file)?;
+    header.check::<S>()?;
+    let data = &file[HEADER_LEN..];
+    if data.len() as u64 != header.len {
+        return Err(TableError::Truncated {
+            found: data.len() as u64,
+            expected: header.len,
+        });
+    }
+    let found = checksum(data);
+    if found != header.checksum {
+        return Err(TableError::Checksum {
+            found,
+            expected: header.checksum,
+        });
+    }
+    Ok(data)
+}
+
+impl LlMoves {
+    /// the algorithms coming with the library
+    fn shipped(self) -> &'static [u8] {
+        match self {
+            #[cfg(feature = "one-look-table")]
+            LlMoves::Htm => checked::<HtmAlgs>(HTM_ALGS)
+                .expect("the algorithms coming with the library match their table version"),
+            _ => &[],
+        }
+    }
+
+    fn load(self, cache: &TableCache) -> Result<Table, TableError> {
+        match self {
+            LlMoves::Htm => cache.load::<HtmAlgs>(),
+            LlMoves::Ru => cache.load::<RuAlgs>(),
+            LlMoves::Ruf => cache.load::<RufAlgs>(),
+        }
+    }
+
+    fn save(self, cache: &TableCache, data: Vec<u8>) -> Result<(), TableError> {
+        fn save<S: TableSpec>(cache: &TableCache, data: Vec<u8>) -> Result<(), TableError> {
+            Ok(Table::from_vec::<S>(data).save(cache.path_of::<S>())?)
+        }
+        match self {
+            LlMoves::Htm => save::<HtmAlgs>(cache, data),
+            LlMoves::Ru => save::<RuAlgs>(cache, data),
+            LlMoves::Ruf => save::<RufAlgs>(cache, data),
+        }
+    }
+}
+
+fn encode(algs: &HashMap<LlState, Option<TurnedAlg>>) -> Vec<u8> {
+    let mut found = algs
+        .iter()
+        .filter_map(|(state, alg)| Some((state, alg.as_ref()?)))
+        .collect::<Vec<_>>();
+    found.sort_by_key(|(state, _)| **state);
+    let mut data = vec![];
+    for (state, (turn, alg)) in found {
+        let pieces = state.corners.iter().chain(&state.edges);
+        data.extend(pieces.flat_map(|(piece, orientation)| [*piece, *orientation]));
+        data.extend([*turn as u8, alg.len() as u8]);
+        data.extend(alg.iter().map(|op| htm_index(op) as u8));
+    }
+    data
+}
+
+/// the algorithms of `data`, `None` past `max_len` moves since they are the shortest,
+/// stopping at a move out of [`HTM`]
+fn decode(data: &[u8], max_len: usize) -> HashMap<LlState, Option<TurnedAlg>> {
+    let mut algs = HashMap::new();
+    let mut rest = data;
+    while let [head @ .., turn, len] = rest.get(..18).unwrap_or_default() {
+        let Some(moves) = rest.get(18..18 + *len as usize) else {
+            break;
+        };
+        let pair = |i: usize| (head[2 * i], head[2 * i + 1]);
+        let state = LlState {
+            corners: std::array::from_fn(pair),
+            edges: std::array::from_fn(|i| pair(i + 4)),
+        };
+        let Some(alg) = moves
+            .iter()
+            .map(|m| HTM.get(*m as usize).copied())
+            .collect::<Option<Vec<_>>>()
+        else {
+            break;
+        };
+        algs.insert(
+            state,
+            (alg.len() <= max_len).then_some((*turn as usize, alg)),
+        );
+        rest = &rest[18 + moves.len()..];
+    }
+    algs
+}
+
+/// Why [`OneLookSolver`] has no algorithm for a cube.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LlSolveError {
+    /// the first two layers are not solved
+    F2lUnsolved,
+    /// the case was never searched, see [`OneLookSolver::precompute`]
+    NotSearched,
+    /// no algorithm of at most the maximum length solves the case with the moves allowed
+    NotFound,
+}
+
+impl Display for LlSolveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LlSolveError::F2lUnsolved => write!(f, "the first two layers are not solved"),
+            LlSolveError::NotSearched => write!(f, "the case was never searched"),
+            LlSolveError::NotFound => write!(f, "no algorithm within the maximum length"),
+        }
+    }
+}
+
+impl Error for LlSolveError {}
+
+/// Finds the shortest algorithm solving a last layer, both AUFs aside, keeping the
+/// algorithm of each case once found.
+///
+/// Searching generates the pattern tables of the moves first, about 90 MB held in memory,
+/// only written to disk by a solver made with [`OneLookSolver::with_cache`]. Cases of up
+/// to 14 face turns then take seconds, every further move multiplying that by about 13.
+pub struct OneLookSolver {
+    moves: LlMoves,
+    max_len: usize,
+    /// where the pattern tables and the algorithms found are saved
+    cache: Option<TableCache>,
+    pruning: OnceLock<Pruning>,
+    /// the algorithm of each canonical state with the turn of U before it, `None` past `max_len`
+    cases: Mutex<HashMap<LlState, Option<TurnedAlg>>>,
+}
+
+/// turns on the same face or out of the order of their axis make a move sequence
+/// equivalent to a shorter or canonical one
+fn is_redundant(prev: Option<&&'static T>, op: &T) -> bool {
+    prev.is_some_and(|prev| {
+        prev.layer().axis() == op.layer().axis() && prev.layer().depth() >= op.layer().depth()
+    })
+}
+
+impl OneLookSolver {
+    /// a solver starting from the algorithms coming with the library, those of every face
+    /// turn, keeping its pattern tables and the algorithms it finds in memory only
+    pub fn new(moves: LlMoves, max_len: usize) -> Self {
+        Self {
+            moves,
+            max_len,
+            cache: None,
+            pruning: OnceLock::new(),
+            cases: Mutex::new(decode(moves.shipped(), max_len)),
+        }
+    }
+
+    /// a solver keeping its pattern tables and the algorithms it finds in `cache`,
+    /// starting from the algorithms coming with the library and those saved there
+    pub fn with_cache(moves: LlMoves, max_len: usize, cache: TableCache) -> Self {
+        let solver = Self::new(moves, max_len);
+        if let Ok(algs) = moves.load(&cache) {
+            solver
+                .cases
+                .lock()
+                .expect("not poisoned")
+                .extend(decode(&algs, max_len));
+        }
+        Self {
+            cache: Some(cache),
+            ..solver
+        }
+    }
+
+    /// the shortest algorithm of at most `max_len` moves solving `rubik` from one of
+    /// its turns of U, looked up among the cases searched: with every face turn, they
+    /// all come with the library
+    pub fn solve(&self, rubik: &Rubik) -> Result<LlSolution, LlSolveError> {
+        let state = LlState::new(rubik).ok_or(LlSolveError::F2lUnsolved)?;
+        let known = self
+            .cases
+            .lock()
+            .expect("not poisoned")
+            .get(&state.canonical())
+            .cloned();
+        let alg = known.ok_or(LlSolveError::NotSearched)?;
+        Ok(self.solution(rubik, state, alg.ok_or(LlSolveError::NotFound)?))
+    }
+
+    /// as [`OneLookSolver::solve`], searching the case first if it never was
+    pub fn search(&self, rubik: &Rubik) -> Result<LlSolution, LlSolveError> {
+        let state = LlState::new(rubik).ok_or(LlSolveError::F2lUnsolved)?;
+        let alg = self.case(state.canonical()).ok_or(LlSolveError::NotFound)?;
+        Ok(self.solution(rubik, state, alg))
+    }
+
+    /// `rubik`, in `state`, solved by the algorithm of its canonical state
+    fn solution(&self, rubik: &Rubik, state: LlState, (turn, alg): TurnedAlg) -> LlSolution {
+        let canonical = state.canonical();
+        // the turns of U bringing the state to its canonical one, a relabeling aside
+        let turns = (0..4)
+            .find(|turns| {
+                let turned = (0..*turns).fold(state, |state, _| state.turn());
+                (0..4)
+                    .scan(turned, |state, _| {
+                        *state = state.relabel();
+                        Some(*state)
+                    })
+                    .any(|state| state == canonical)
+            })
+            .expect("a turn of the canonical state");
+        let pre_auf = AUF[(turns + turn) % 4];
+        let mut solved = rubik.clone();
+        pre_auf.apply_on(&mut solved);
+        alg.iter().for_each(|op| op.apply_on(&mut solved));
+        let post_auf = AUF
+            .into_iter()
+            .find(|auf| {
+                let mut rubik = solved.clone();
+                auf.apply_on(&mut rubik);
+                rubik.is_solved()
+            })
+            .expect("the algorithm solves the last layer");
+        LlSolution {
+            pre_auf,
+            alg,
+            post_auf,
+        }
+    }
+
+    /// searches every case of the states `set` applies to not searched yet,
+    /// [`LlSet::OneLook`] for all of them, returning the number of cases solved within
+    /// `max_len` moves, the solved one included; [`OneLookSolver::solve`] then looks
+    /// them up.
+    ///
+    /// Cases are searched on every core, each search also solving the inverse case and,
+    /// with every face turn, the mirrored ones. With every face turn, the 3916 cases of
+    /// [`LlSet::OneLook`] take up to 16 moves, and took about five and a half hours on
+    /// one core in a release build.
+    ///
+    /// The algorithms are saved to the cache if any, a cache that cannot be written to
+    /// only keeping them for this solver.
+    pub fn precompute(&self, set: LlSet) -> usize {
+        let mut cases = LlState::all()
+            .filter(|state| set.applies_to(state))
+            .map(LlState::canonical)
+            .collect::<Vec<_>>();
+        cases.sort();
+        cases.dedup();
+        let next = AtomicUsize::new(0);
+        let threads = std::thread::available_parallelism().map_or(1, usize::from);
+        std::thread::scope(|scope| {
+            for _ in 0..threads {
+                scope.spawn(|| {
+                    while let Some(case) = cases.get(next.fetch_add(1, Ordering::Relaxed)) {
+                        self.case(*case);
+                    }
+                });
+            }
+        });
+        let solved = cases
+            .into_iter()
+            .filter(|case| self.case(*case).is_some())
+            .count();
+        let _ = self.save();
+        solved
+    }
+
+    /// saves the algorithms found so far to the cache, along with those saved there,
+    /// doing nothing without a cache
+    pub fn save(&self) -> Result<(), TableError> {
+        let Some(cache) = &self.cache else {
+            return Ok(());
+        };
+        let mut algs = self
+            .moves
+            .load(cache)
+            .map(|algs| decode(&algs, usize::MAX))
+            .unwrap_or_default();
+        algs.extend(
+            self.cases
+                .lock()
+                .expect("not poisoned")
+                .iter()
+                .filter(|(_, alg)| alg.is_some())
+                .map(|(state, alg)| (*state, alg.clone())),
+        );
+        self.moves.save(cache, encode(&algs))
+    }
+
+    /// the algorithm of a canonical state, searched for unless already known
+    fn case(&self, canonical: LlState) -> Option<TurnedAlg> {
+        let known = self
+            .cases
+            .lock()
+            .expect("not poisoned")
+            .get(&canonical)
+            .cloned();
+        known.unwrap_or_else(|| {
+            let found = self.find(canonical);
+            let symmetric = self.symmetric(canonical, found.as_ref());
+            let mut cases = self.cases.lock().expect("not poisoned");
+            for (state, alg) in symmetric {
+                cases.entry(state).or_insert(alg);
+            }
+            cases.insert(canonical, found.clone());
+            found
+        })
+    }
+
+    /// the canonical states symmetric to `canonical` with the moves allowed, each with
+    /// `found` made into its algorithm, as short as any since symmetries keep lengths
+    fn symmetric(
+        &self,
+        canonical: LlState,
+        found: Option<&TurnedAlg>,
+    ) -> Vec<(LlState, Option<TurnedAlg>)> {
+        let Some(setup) = canonical.setup() else {
+            return vec![];
+        };
+        let state = |ops: &[&'static T]| {
+            let mut rubik = Rubik::new();
+            ops.iter().for_each(|op| op.apply_on(&mut rubik));
+            rubik
+        };
+        type Image = fn(&[&'static T]) -> Vec<&'static T>;
+        let mut images: Vec<(Rubik, Image)> = vec![(state(&setup).inverse(), |alg| invert(alg))];
+        if self.moves == LlMoves::Htm {
+            let mirrored = state(&mirror(&setup));
+            images.push((mirrored.inverse(), |alg| invert(&mirror(alg))));
+            images.push((mirrored, mirror));
+        }
+        images
+            .into_iter()
+            .filter_map(|(rubik, image)| {
+                let state = LlState::new(&rubik)?.canonical();
+                match found {
+                    Some((_, alg)) => {
+                        let alg = image(alg);
+                        Some((state, Some((turn_before(state, &alg)?, alg))))
+                    }
+                    None => Some((state, None)),
+                }
+            })
+            .collect()
+    }
+
+    /// the shortest algorithm solving `state` after some turns of U, deepening the
+    /// search from each of them in turn
+    fn find(&self, state: LlState) -> Option<TurnedAlg> {
+        let setup = state.setup()?;
+        let pruning = self
+            .pruning
+            .get_or_init(|| Pruning::new(self.moves, self.cache.as_ref()));
+        // solving the state with a post-AUF solves it outright once the inverse of the
+        // post-AUF is done first
+        let starts = AUF
+            .into_iter()
+            .enumerate()
+            .flat_map(|(turn, pre_auf)| {
+                let setup = &setup;
+                self.moves.post_aufs().iter().map(move |post_auf| {
+                    let mut rubik = Rubik::new();
+                    post_auf.inverse().apply_on(&mut rubik);
+                    setup.iter().for_each(|op| op.apply_on(&mut rubik));
+                    pre_auf.apply_on(&mut rubik);
+                    (turn, Coords::of(&rubik))
+                })
+            })
+            .collect::<Vec<_>>();
+        let moves = self
+            .moves
+            .moves()
+            .iter()
+            .map(|op| (*op, htm_index(op)))
+            .collect::<Vec<_>>();
+        let least = starts.iter().map(|(_, start)| pruning.dist(start)).min()?;
+        for len in least..=self.max_len {
+            for (turn, start) in &starts {
+                let mut path = Vec::with_capacity(len);
+                if extend(pruning, &moves, *start, len, &mut path) {
+                    return Some((*turn, path));
+                }
+            }
+        }
+        None
+    }
+}
+
+/// the moves mirrored through the plane between L and R
+fn mirror(ops: &[&'static T]) -> Vec<&'static T> {
+    ops.iter()
+        .map(|op| {
+            let face = match op.face().expect("algorithms are made of face turns") {
+                CubeFace::R => CubeFace::L,
+                CubeFace::L => CubeFace::R,
+                face => face,
+            };
+            T::face_turn(face, op.ptr_rotate().inverse())
+        })
+        .collect()
+}
+
+/// the turns of U before `alg` that solve the canonical `state` up to a post-AUF
+fn turn_before(state: LlState, alg: &[&'static T]) -> Option<usize> {
+    let mut rubik = Rubik::new();
+    state.setup()?.iter().for_each(|op| op.apply_on(&mut rubik));
+    (0..4).find(|turn| {
+        let mut rubik = rubik.clone();
+        AUF[*turn].apply_on(&mut rubik);
+        alg.iter().for_each(|op| op.apply_on(&mut rubik));
+        AUF.iter().any(|auf| {
+            let mut rubik = rubik.clone();
+            auf.apply_on(&mut rubik);
+            rubik.is_solved()
+        })
+    })
+}
+
+/// whether `path` extends by `left` of `moves` into an algorithm solving `coords`,
+/// pushing them onto `path` if so
+fn extend(
+    pruning: &Pruning,
+    moves: &[(&'static T, usize)],
+    coords: Coords,
+    left: usize,
+    path: &mut Vec<&'static T>,
+) -> bool {
+    if left == 0 {
+        return pruning.is_solved(&coords);
+    }
+    if pruning.dist(&coords) > left {
+        return false;
+    }
+    for (op, index) in moves {
+        // a turn of U first is a pre-AUF and one last a post-AUF, both left out
+        let auf = op.face() == Some(CubeFace::U);
+        if auf && (path.is_empty() || left == 1) || is_redundant(path.last(), op) {
+            continue;
+        }
+        path.push(op);
+        if extend(pruning, moves, coords.turn(*index), left - 1, path) {
+            return true;
+        }
+        path.pop();
+    }
+    false
+}
@@ -0,0 +1,471 @@
+//! Pattern tables for the one-look search: how many moves of a [`LlMoves`] set at least
+//! bring the corners, or one of two groups of six edges, back to a solved cube.
+//!
+//! The cube is followed as coordinates, the permutation and twist of the corners and
+//! the slots and flips of each edge group, turned by move tables instead of
+//! [`RubikLayerTransform::apply_on`]. Distances are stored as 4-bit entries, two to a
+//! byte, in tables cached through [`TableCache`].
+use std::{marker::PhantomData, sync::OnceLock};
+
+use crate::{
+    prelude::RubikLayerTransform,
+    solver::enumerate::HTM,
+    tables::{Table, TableCache, TableSpec},
+    CubePosition, Rubik,
+};
+
+use super::LlMoves;
+
+/// corner slots, U layer first
+const CORNERS: [CubePosition; 8] = [
+    CubePosition::FUL,
+    CubePosition::FUR,
+    CubePosition::BUR,
+    CubePosition::BUL,
+    CubePosition::FDL,
+    CubePosition::FDR,
+    CubePosition::BDR,
+    CubePosition::BDL,
+];
+/// edge slots, the U layer and the front E edges, then the D layer and the back E edges
+const EDGES: [CubePosition; 12] = [
+    CubePosition::FU,
+    CubePosition::UR,
+    CubePosition::BU,
+    CubePosition::UL,
+    CubePosition::FR,
+    CubePosition::FL,
+    CubePosition::FD,
+    CubePosition::DLR,
+    CubePosition::BD,
+    CubePosition::DLL,
+    CubePosition::BR,
+    CubePosition::BL,
+];
+/// the edges followed by each edge table, by their index in [`EDGES`]
+const GROUPS: [[u8; 6]; 2] = [[0, 1, 2, 3, 4, 5], [6, 7, 8, 9, 10, 11]];
+
+const MOVES: usize = HTM.len();
+const CORNER_PERMS: usize = 40320;
+const CORNER_TWISTS: usize = 2187;
+/// the ways to place six edges in twelve slots
+const EDGE_PLACES: usize = 12 * 11 * 10 * 9 * 8 * 7;
+const EDGE_FLIPS: usize = 64;
+/// the entry of a state not reached, never met from a valid cube
+const UNSEEN: u8 = 0xf;
+
+/// A cube as the piece and orientation in each corner and edge slot, pieces and slots
+/// numbered as in [`CORNERS`] and [`EDGES`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Cubie {
+    cp: [u8; 8],
+    co: [u8; 8],
+    ep: [u8; 12],
+    eo: [u8; 12],
+}
+
+impl Cubie {
+    fn of(rubik: &Rubik) -> Self {
+        let index = |slots: &[CubePosition], slot| {
+            slots
+                .iter()
+                .position(|at| *at == slot)
+                .expect("a corner stays a corner and an edge an edge") as u8
+        };
+        let mut cubie = Self {
+            cp: [0; 8],
+            co: [0; 8],
+            ep: [0; 12],
+            eo: [0; 12],
+        };
+        for (i, slot) in CORNERS.into_iter().enumerate() {
+            let cube = rubik.cubes[slot as usize];
+            cubie.cp[i] = index(&CORNERS, cube.home(slot));
+            cubie.co[i] = cube.orientation(slot);
+        }
+        for (i, slot) in EDGES.into_iter().enumerate() {
+            let cube = rubik.cubes[slot as usize];
+            cubie.ep[i] = index(&EDGES, cube.home(slot));
+            cubie.eo[i] = cube.orientation(slot);
+        }
+        cubie
+    }
+}
+
+/// the cube each move of [`HTM`] makes of a solved one
+fn move_cubies() -> [Cubie; MOVES] {
+    HTM.map(|op| {
+        let mut rubik = Rubik::new();
+        op.apply_on(&mut rubik);
+        Cubie::of(&rubik)
+    })
+}
+
+/// the rank of a permutation of `0..N` in lexicographic order
+fn perm_index<const N: usize>(perm: &[u8; N]) -> usize {
+    (0..N).fold(0, |index, i| {
+        let smaller = perm[i + 1..].iter().filter(|p| **p < perm[i]).count();
+        index * (N - i) + smaller
+    })
+}
+
+fn perm_of<const N: usize>(mut index: usize) -> [u8; N] {
+    let mut digits = [0; N];
+    for i in (0..N).rev() {
+        digits[i] = index % (N - i);
+        index /= N - i;
+    }
+    let mut left = (0..N as u8).collect::<Vec<_>>();
+    digits.map(|digit| left.remove(digit))
+}
+
+/// the twists of the first seven corners in base 3, the last one following from them
+fn twist_index(co: &[u8; 8]) -> usize {
+    co[..7]
+        .iter()
+        .rev()
+        .fold(0, |index, o| index * 3 + *o as usize)
+}
+
+fn twist_of(mut index: usize) -> [u8; 8] {
+    let mut co = [0; 8];
+    for o in &mut co[..7] {
+        *o = (index % 3) as u8;
+        index /= 3;
+    }
+    co[7] = (3 - co[..7].iter().sum::<u8>() % 3) % 3;
+    co
+}
+
+/// the slots of six edges, each ranked among the slots the edges before it leave
+fn place_index(slots: &[u8; 6]) -> usize {
+    (0..6).fold(0, |index, i| {
+        let before = slots[..i].iter().filter(|s| **s < slots[i]).count();
+        index * (12 - i) + slots[i] as usize - before
+    })
+}
+
+fn place_of(mut index: usize) -> [u8; 6] {
+    let mut digits = [0; 6];
+    for i in (0..6).rev() {
+        digits[i] = index % (12 - i);
+        index /= 12 - i;
+    }
+    let mut left = (0..12).collect::<Vec<u8>>();
+    digits.map(|digit| left.remove(digit))
+}
+
+/// Where each move of [`HTM`] takes every coordinate, `MOVES` entries per coordinate.
+struct MoveTables {
+    perm: Vec<u16>,
+    twist: Vec<u16>,
+    place: Vec<u32>,
+    /// the flips of the six edges changed by the move, as a mask to xor
+    flip: Vec<u8>,
+}
+
+fn move_tables() -> &'static MoveTables {
+    static TABLES: OnceLock<MoveTables> = OnceLock::new();
+    TABLES.get_or_init(|| {
+        let moves = move_cubies();
+        let mut tables = MoveTables {
+            perm: Vec::with_capacity(CORNER_PERMS * MOVES),
+            twist: Vec::with_capacity(CORNER_TWISTS * MOVES),
+            place: Vec::with_capacity(EDGE_PLACES * MOVES),
+            flip: Vec::with_capacity(EDGE_PLACES * MOVES),
+        };
+        for index in 0..CORNER_PERMS {
+            let cp = perm_of::<8>(index);
+            for m in &moves {
+                let cp = m.cp.map(|from| cp[from as usize]);
+                tables.perm.push(perm_index(&cp) as u16);
+            }
+        }
+        for index in 0..CORNER_TWISTS {
+            let co = twist_of(index);
+            for m in &moves {
+                let co = std::array::from_fn(|i| (co[m.cp[i] as usize] + m.co[i]) % 3);
+                tables.twist.push(twist_index(&co) as u16);
+            }
+        }
+        for index in 0..EDGE_PLACES {
+            let slots = place_of(index);
+            for m in &moves {
+                let mut flip = 0;
+                let slots = std::array::from_fn(|k| {
+                    let to =
+                        m.ep.iter()
+                            .position(|from| *from == slots[k])
+                            .expect("every slot is filled");
+                    flip |= m.eo[to] << k;
+                    to as u8
+                });
+                tables.place.push(place_index(&slots) as u32);
+                tables.flip.push(flip);
+            }
+        }
+        tables
+    })
+}
+
+/// A cube as the coordinates the pattern tables are indexed by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) struct Coords {
+    perm: u16,
+    twist: u16,
+    places: [u32; 2],
+    flips: [u8; 2],
+}
+
+impl Coords {
+    pub(super) fn of(rubik: &Rubik) -> Self {
+        let cubie = Cubie::of(rubik);
+        let group = |group: &[u8; 6]| {
+            let slots = group.map(|piece| {
+                cubie
+                    .ep
+                    .iter()
+                    .position(|at| *at == piece)
+                    .expect("every edge is somewhere") as u8
+            });
+            let flips = (0..6).fold(0, |flips, k| flips | cubie.eo[slots[k] as usize] << k);
+            (place_index(&slots) as u32, flips)
+        };
+        let groups = GROUPS.map(|pieces| group(&pieces));
+        Self {
+            perm: perm_index(&cubie.cp) as u16,
+            twist: twist_index(&cubie.co) as u16,
+            places: groups.map(|(place, _)| place),
+            flips: groups.map(|(_, flips)| flips),
+        }
+    }
+
+    /// the coordinates after the move of index `m` in [`HTM`]
+    pub(super) fn turn(self, m: usize) -> Self {
+        let tables = move_tables();
+        Self {
+            perm: tables.perm[self.perm as usize * MOVES + m],
+            twist: tables.twist[self.twist as usize * MOVES + m],
+            places: self
+                .places
+                .map(|place| tables.place[place as usize * MOVES + m]),
+            flips: [0, 1].map(|g| self.flips[g] ^ tables.flip[self.places[g] as usize * MOVES + m]),
+        }
+    }
+}
+
+fn solved() -> Coords {
+    Coords::of(&Rubik::new())
+}
+
+fn entry(table: &[u8], index: usize) -> u8 {
+    table[index / 2] >> (index % 2 * 4) & 0xf
+}
+
+fn set_entry(table: &mut [u8], index: usize, dist: u8) {
+    let shift = index % 2 * 4;
+    table[index / 2] = table[index / 2] & !(0xf << shift) | dist << shift;
+}
+
+/// Breadth first search from `goal` over `rows * cols` states with the moves of index
+/// `moves` in [`HTM`], `row` and `col` giving where a move takes the row and the column
+/// of a state, with its row for the column.
+///
+/// States are scanned row by row at each depth, so that the row of a state is only
+/// turned once for all of its columns, skipping the rows nothing was reached in at the
+/// depth before: a few moves only reach a small part of the states.
+fn distances(
+    rows: usize,
+    cols: usize,
+    goal: usize,
+    moves: &[usize],
+    row: impl Fn(usize, usize) -> usize,
+    col: impl Fn(usize, usize, usize) -> usize,
+) -> Vec<u8> {
+    let mut table = vec![UNSEEN << 4 | UNSEEN; (rows * cols).div_ceil(2)];
+    set_entry(&mut table, goal, 0);
+    let mut active = vec![false; rows];
+    active[goal / cols] = true;
+    let mut frontier = Vec::with_capacity(cols);
+    for depth in 0..UNSEEN - 1 {
+        let mut reached = vec![false; rows];
+        for r in (0..rows).filter(|r| active[*r]) {
+            frontier.clear();
+            frontier.extend((0..cols).filter(|c| entry(&table, r * cols + c) == depth));
+            for m in moves {
+                let next = row(r, *m);
+                for c in &frontier {
+                    let index = next * cols + col(r, *c, *m);
+                    if entry(&table, index) == UNSEEN {
+                        set_entry(&mut table, index, depth + 1);
+                        reached[next] = true;
+                    }
+                }
+            }
+        }
+        if !reached.contains(&true) {
+            break;
+        }
+        active = reached;
+    }
+    table
+}
+
+/// A move set the pattern tables are searched with.
+trait PatternMoves: 'static {
+    const SET: LlMoves;
+    /// the names of the corner table and of the two edge tables
+    const NAMES: [&'static str; 3];
+
+    /// the index in [`HTM`] of each move of the set
+    fn indices() -> Vec<usize> {
+        Self::SET.moves().iter().map(|op| htm_index(op)).collect()
+    }
+}
+
+struct Htm;
+struct Ru;
+struct Ruf;
+
+impl PatternMoves for Htm {
+    const SET: LlMoves = LlMoves::Htm;
+    const NAMES: [&'static str; 3] = [
+        "one_look_corners",
+        "one_look_up_edges",
+        "one_look_down_edges",
+    ];
+}
+
+impl PatternMoves for Ru {
+    const SET: LlMoves = LlMoves::Ru;
+    const NAMES: [&'static str; 3] = [
+        "one_look_ru_corners",
+        "one_look_ru_up_edges",
+        "one_look_ru_down_edges",
+    ];
+}
+
+impl PatternMoves for Ruf {
+    const SET: LlMoves = LlMoves::Ruf;
+    const NAMES: [&'static str; 3] = [
+        "one_look_ruf_corners",
+        "one_look_ruf_up_edges",
+        "one_look_ruf_down_edges",
+    ];
+}
+
+/// the corner permutation and twist, `perm * CORNER_TWISTS + twist`
+struct CornerTable<M>(PhantomData<M>);
+
+impl<M: PatternMoves> TableSpec for CornerTable<M> {
+    const NAME: &'static str = M::NAMES[0];
+    const VERSION: u32 = 1;
+    fn generate() -> Vec<u8> {
+        let tables = move_tables();
+        distances(
+            CORNER_PERMS,
+            CORNER_TWISTS,
+            solved().perm as usize * CORNER_TWISTS + solved().twist as usize,
+            &M::indices(),
+            |perm, m| tables.perm[perm * MOVES + m] as usize,
+            |_, twist, m| tables.twist[twist * MOVES + m] as usize,
+        )
+    }
+}
+
+/// the slots and flips of the edges of a group, `place * EDGE_FLIPS + flips`
+fn edge_table<M: PatternMoves>(group: usize) -> Vec<u8> {
+    let tables = move_tables();
+    distances(
+        EDGE_PLACES,
+        EDGE_FLIPS,
+        solved().places[group] as usize * EDGE_FLIPS + solved().flips[group] as usize,
+        &M::indices(),
+        |place, m| tables.place[place * MOVES + m] as usize,
+        |place, flips, m| flips ^ tables.flip[place * MOVES + m] as usize,
+    )
+}
+
+/// the edges of the U layer with FR and FL
+struct UpEdgeTable<M>(PhantomData<M>);
+
+impl<M: PatternMoves> TableSpec for UpEdgeTable<M> {
+    const NAME: &'static str = M::NAMES[1];
+    const VERSION: u32 = 1;
+    fn generate() -> Vec<u8> {
+        edge_table::<M>(0)
+    }
+}
+
+/// the edges of the D layer with BR and BL
+struct DownEdgeTable<M>(PhantomData<M>);
+
+impl<M: PatternMoves> TableSpec for DownEdgeTable<M> {
+    const NAME: &'static str = M::NAMES[2];
+    const VERSION: u32 = 1;
+    fn generate() -> Vec<u8> {
+        edge_table::<M>(1)
+    }
+}
+
+/// The pattern tables of a move set.
+pub(super) struct Pruning {
+    corners: Table,
+    edges: [Table; 2],
+    solved: Coords,
+}
+
+impl Pruning {
+    /// the tables of `moves`, loaded from `cache` or generated and saved there, only
+    /// generated without a cache
+    pub(super) fn new(moves: LlMoves, cache: Option<&TableCache>) -> Self {
+        match moves {
+            LlMoves::Htm => Self::load::<Htm>(cache),
+            LlMoves::Ru => Self::load::<Ru>(cache),
+            LlMoves::Ruf => Self::load::<Ruf>(cache),
+        }
+    }
+
+    fn load<M: PatternMoves>(cache: Option<&TableCache>) -> Self {
+        fn table<S: TableSpec>(cache: Option<&TableCache>) -> Table {
+            match cache {
+                Some(cache) => cache.load_or_generate::<S>(),
+                None => Table::generate::<S>(),
+            }
+        }
+        Self {
+            corners: table::<CornerTable<M>>(cache),
+            edges: [
+                table::<UpEdgeTable<M>>(cache),
+                table::<DownEdgeTable<M>>(cache),
+            ],
+            solved: solved(),
+        }
+    }
+
+    /// the fewest moves of the set solving `coords`, at least
+    pub(super) fn dist(&self, coords: &Coords) -> usize {
+        let corners = entry(
+            &self.corners,
+            coords.perm as usize * CORNER_TWISTS + coords.twist as usize,
+        );
+        let edges = [0, 1].map(|g| {
+            entry(
+                &self.edges[g],
+                coords.places[g] as usize * EDGE_FLIPS + coords.flips[g] as usize,
+            )
+        });
+        corners.max(edges[0]).max(edges[1]) as usize
+    }
+
+    pub(super) fn is_solved(&self, coords: &Coords) -> bool {
+        *coords == self.solved
+    }
+}
+
+/// the index of `op` in [`HTM`]
+pub(super) fn htm_index(op: &RubikLayerTransform) -> usize {
+    HTM.iter()
+        .position(|m| *m == op)
+        .expect("algorithms are made of face turns")
+}
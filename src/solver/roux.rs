@@ -0,0 +1,218 @@
+//! Roux solving: first block, second block, CMLL and the last six edges.
+use std::sync::Arc;
+
+use crate::{cube::CubeFace, prelude::RubikLayerTransform as T, CubePosition, Rubik};
+
+use super::{
+    enumerate::{EnumerateSolver, HTM},
+    last_layer::{last_layer, AUF},
+    mask::{GoalMask, MaskSolver},
+    steps::{collect_steps, macro_step, Alg, Macro, Step},
+    Goal, RubikSolveState, RubikSolver,
+};
+
+/// the moves of the second block, `r` being `R M'`
+pub const SECOND_BLOCK_MOVES: [&T; 9] = [
+    &T::R,
+    &T::R2,
+    &T::RI,
+    &T::U,
+    &T::U2,
+    &T::UI,
+    &T::M,
+    &T::M2,
+    &T::MI,
+];
+
+/// the moves of the last six edges
+pub const LSE_MOVES: [&T; 6] = [&T::M, &T::M2, &T::MI, &T::U, &T::U2, &T::UI];
+
+/// The 42 CMLL cases, each algorithm solving the U corners of its case up to turns of U
+/// before and after. Cases are named after the COLL case with the same corners as
+/// [`super::last_layer::LlSet::named_cases`] lists it, but for the two swaps of oriented
+/// corners, `CMLL O adjacent` and `CMLL O diagonal`.
+///
+/// The algorithms are usual human ones in face turns, mostly `<R, U, F>`: Sune and its
+/// variations, the corner cases of OLL and COLL, the J and Y perms for the oriented
+/// corners, their mirrors and inverses, and for a few cases two of them one after the
+/// other. Keeping the first two layers, they keep both blocks whatever the U edges and
+/// the M slice.
+#[rustfmt::skip]
+pub const CMLL: [Alg; 42] = [
+    Alg::new("CMLL AS 1", &[&T::R, &T::U2, &T::RI, &T::UI, &T::R, &T::UI, &T::RI]),
+    Alg::new("CMLL AS 2", &[&T::R, &T::U2, &T::RI, &T::U2, &T::RI, &T::F, &T::R, &T::FI]),
+    Alg::new("CMLL AS 3", &[&T::FI, &T::L, &T::F, &T::LI, &T::U2, &T::LI, &T::U2, &T::L]),
+    Alg::new("CMLL AS 4", &[&T::LI, &T::U, &T::R, &T::UI, &T::L, &T::U, &T::RI]),
+    Alg::new("CMLL AS 5", &[&T::R, &T::U2, &T::RI, &T::UI, &T::R, &T::UI, &T::RI, &T::F, &T::RI, &T::FI, &T::R, &T::U, &T::R, &T::UI, &T::RI]),
+    Alg::new("CMLL AS 6", &[&T::R, &T::U2, &T::RI, &T::F, &T::RI, &T::FI, &T::R, &T::UI, &T::R, &T::UI, &T::RI]),
+    Alg::new("CMLL H 1", &[&T::R, &T::U, &T::RI, &T::U, &T::R, &T::UI, &T::RI, &T::U, &T::R, &T::U2, &T::RI]),
+    Alg::new("CMLL H 2", &[&T::RI, &T::U2, &T::R, &T::U2, &T::R2, &T::FI, &T::R, &T::U, &T::R, &T::UI, &T::RI, &T::F, &T::U, &T::R]),
+    Alg::new("CMLL H 3", &[&T::F, &T::RI, &T::FI, &T::R, &T::U, &T::RI, &T::F, &T::R, &T::F2, &T::U, &T::F]),
+    Alg::new("CMLL H 4", &[&T::F, &T::R, &T::U, &T::RI, &T::UI, &T::R, &T::U, &T::RI, &T::UI, &T::R, &T::U, &T::RI, &T::UI, &T::FI]),
+    Alg::new("CMLL L 1", &[&T::R, &T::U, &T::RI, &T::U, &T::R, &T::UI, &T::RI, &T::U, &T::R, &T::UI, &T::RI, &T::U, &T::R, &T::U2, &T::RI]),
+    Alg::new("CMLL L 2", &[&T::RI, &T::FI, &T::R, &T::U, &T::RI, &T::UI, &T::RI, &T::F, &T::R2, &T::UI, &T::RI, &T::U2, &T::R]),
+    Alg::new("CMLL L 3", &[&T::F, &T::R, &T::UI, &T::RI, &T::UI, &T::R, &T::U, &T::RI, &T::FI]),
+    Alg::new("CMLL L 4", &[&T::RI, &T::U2, &T::RI, &T::DI, &T::R, &T::U2, &T::RI, &T::D, &T::R2]),
+    Alg::new("CMLL L 5", &[&T::F, &T::RI, &T::FI, &T::R, &T::U, &T::R, &T::UI, &T::RI]),
+    Alg::new("CMLL L 6", &[&T::R, &T::U2, &T::R2, &T::F, &T::R, &T::FI, &T::R, &T::U2, &T::RI]),
+    Alg::new("CMLL O adjacent", &[&T::R, &T::U, &T::RI, &T::FI, &T::R, &T::U, &T::RI, &T::UI, &T::RI, &T::F, &T::R2, &T::UI, &T::RI]),
+    Alg::new("CMLL O diagonal", &[&T::F, &T::R, &T::UI, &T::RI, &T::UI, &T::R, &T::U, &T::RI, &T::FI, &T::R, &T::U, &T::RI, &T::UI, &T::RI, &T::F, &T::R, &T::FI]),
+    Alg::new("CMLL Pi 1", &[&T::R, &T::U2, &T::R2, &T::UI, &T::R2, &T::UI, &T::R2, &T::U2, &T::R]),
+    Alg::new("CMLL Pi 2", &[&T::R, &T::U2, &T::RI, &T::UI, &T::R, &T::U, &T::RI, &T::U2, &T::RI, &T::F, &T::R, &T::FI]),
+    Alg::new("CMLL Pi 3", &[&T::FI, &T::UI, &T::F2, &T::RI, &T::FI, &T::R, &T::UI, &T::RI, &T::F, &T::R, &T::FI]),
+    Alg::new("CMLL Pi 4", &[&T::RI, &T::UI, &T::FI, &T::R, &T::U, &T::RI, &T::UI, &T::RI, &T::F, &T::R2, &T::U2, &T::RI, &T::U2, &T::R]),
+    Alg::new("CMLL Pi 5", &[&T::F, &T::RI, &T::FI, &T::R, &T::U2, &T::R, &T::UI, &T::RI, &T::U, &T::R, &T::U2, &T::RI]),
+    Alg::new("CMLL Pi 6", &[&T::R, &T::U2, &T::RI, &T::F, &T::RI, &T::FI, &T::UI, &T::R2, &T::UI, &T::R2, &T::U2, &T::R]),
+    Alg::new("CMLL S 1", &[&T::R, &T::U, &T::RI, &T::U, &T::R, &T::U2, &T::RI]),
+    Alg::new("CMLL S 2", &[&T::F, &T::RI, &T::FI, &T::R, &T::U2, &T::R, &T::U2, &T::RI]),
+    Alg::new("CMLL S 3", &[&T::LI, &T::U2, &T::L, &T::U2, &T::L, &T::FI, &T::LI, &T::F]),
+    Alg::new("CMLL S 4", &[&T::R, &T::U, &T::RI, &T::UI, &T::RI, &T::F, &T::R, &T::FI, &T::R, &T::U, &T::RI, &T::U, &T::R, &T::U2, &T::RI]),
+    Alg::new("CMLL S 5", &[&T::R, &T::UI, &T::LI, &T::U, &T::RI, &T::UI, &T::L]),
+    Alg::new("CMLL S 6", &[&T::R, &T::U, &T::RI, &T::U, &T::RI, &T::F, &T::R, &T::FI, &T::R, &T::U2, &T::RI]),
+    Alg::new("CMLL T 1", &[&T::F, &T::RI, &T::F, &T::R2, &T::UI, &T::RI, &T::UI, &T::R, &T::U, &T::RI, &T::F2]),
+    Alg::new("CMLL T 2", &[&T::F, &T::RI, &T::FI, &T::R, &T::U, &T::R, &T::U, &T::R2, &T::F, &T::R, &T::FI, &T::R, &T::U2, &T::RI]),
+    Alg::new("CMLL T 3", &[&T::F, &T::R, &T::UI, &T::RI, &T::U, &T::R, &T::U, &T::RI, &T::FI]),
+    Alg::new("CMLL T 4", &[&T::R, &T::U, &T::RI, &T::UI, &T::RI, &T::F, &T::R, &T::FI]),
+    Alg::new("CMLL T 5", &[&T::R, &T::U2, &T::RI, &T::F2, &T::R, &T::U2, &T::RI, &T::U2, &T::RI, &T::F2, &T::R]),
+    Alg::new("CMLL T 6", &[&T::R, &T::U2, &T::RI, &T::UI, &T::R, &T::U, &T::RI, &T::F, &T::RI, &T::FI, &T::R2, &T::U2, &T::RI]),
+    Alg::new("CMLL U 1", &[&T::F2, &T::R, &T::UI, &T::RI, &T::U, &T::R, &T::U, &T::R2, &T::FI, &T::R, &T::FI]),
+    Alg::new("CMLL U 2", &[&T::F, &T::R2, &T::D, &T::RI, &T::U, &T::R, &T::DI, &T::R2, &T::UI, &T::FI]),
+    Alg::new("CMLL U 3", &[&T::RI, &T::U2, &T::R, &T::U, &T::R2, &T::FI, &T::R, &T::U, &T::R, &T::UI, &T::RI, &T::F, &T::R]),
+    Alg::new("CMLL U 4", &[&T::R2, &T::DI, &T::R, &T::U2, &T::RI, &T::D, &T::R, &T::U2, &T::R]),
+    Alg::new("CMLL U 5", &[&T::RI, &T::F2, &T::R, &T::U2, &T::R, &T::U2, &T::RI, &T::F2, &T::R, &T::U2, &T::RI]),
+    Alg::new("CMLL U 6", &[&T::F, &T::R, &T::U, &T::RI, &T::UI, &T::FI]),
+];
+
+/// Solves the cube with the Roux method, blocks on L and R and the last six edges
+/// in `<M, U>`, annotating every stage.
+///
+/// Both blocks are solved optimally, the first one in face turns and the second one
+/// in `<R, U, M>`. CMLL is one algorithm of [`CMLL`], and each stage of the last six
+/// edges is solved optimally. Slice moves are kept as `M`, `M'` and `M2`.
+/// The centers must be in their home slots, as after any scramble of face turns.
+pub struct RouxSolver;
+
+/// the 1x2x3 block on the D half of the `side` face
+fn block(side: CubeFace) -> GoalMask {
+    GoalMask::new().solve(CubePosition::all().filter(|p| {
+        p.faces().any(|face| face == side) && p.faces().all(|face| face != CubeFace::U)
+    }))
+}
+
+fn position(faces: impl IntoIterator<Item = CubeFace>) -> CubePosition {
+    CubePosition::from_faces(faces).expect("faces of a slot are never opposite")
+}
+
+/// `mask` reached once U is adjusted and, if need be, the M slice turned by a half turn
+struct LseGoal(GoalMask);
+
+impl Goal for LseGoal {
+    fn is_reached(&self, rubik: &Rubik) -> bool {
+        AUF.iter().any(|auf| {
+            [&T::UNIT, &T::M2].iter().any(|m2| {
+                let mut rubik = rubik.clone();
+                auf.apply_on(&mut rubik);
+                m2.apply_on(&mut rubik);
+                self.0.is_reached(&rubik)
+            })
+        })
+    }
+}
+
+fn lse_step(rubik: &mut Rubik, name: &str, explanation: &str, solver: EnumerateSolver) -> Step {
+    let (solved, ops) = solver.solve(rubik.clone()).collect();
+    *rubik = solved;
+    Step {
+        name: name.to_string(),
+        explanation: explanation.to_string(),
+        ops,
+    }
+}
+
+impl RouxSolver {
+    pub fn steps(&self, rubik: Rubik) -> Vec<Step> {
+        let mut steps = vec![];
+
+        let first_block = block(CubeFace::L);
+        let (mut rubik, ops) = MaskSolver::htm(&first_block).solve(rubik).collect();
+        steps.push(Step {
+            name: "first block".to_string(),
+            explanation: "Build the 1x2x3 block on the D half of L, around the L center."
+                .to_string(),
+            ops,
+        });
+
+        let blocks = first_block.union(&block(CubeFace::R));
+        let (solved, ops) = MaskSolver::new(&blocks, Arc::new(SECOND_BLOCK_MOVES), 20)
+            .solve(rubik)
+            .collect();
+        rubik = solved;
+        steps.push(Step {
+            name: "second block".to_string(),
+            explanation: "Build the 1x2x3 block on the D half of R with <R, U, M>, \
+                          leaving the first block untouched."
+                .to_string(),
+            ops,
+        });
+
+        let corners = blocks.clone().solve(last_layer().filter(|p| p.is_corner()));
+        let macros = CMLL.iter().flat_map(Macro::with_auf).collect::<Vec<_>>();
+        steps.push(macro_step(
+            &mut rubik,
+            "CMLL".to_string(),
+            "Solve the U corners up to a turn of U, ignoring the U edges and the M slice, \
+             with one of the 42 CMLL algorithms.",
+            &macros,
+            1,
+            |rubik| {
+                AUF.iter().any(|auf| {
+                    let mut rubik = rubik.clone();
+                    auf.apply_on(&mut rubik);
+                    corners.is_reached(&rubik)
+                })
+            },
+        ));
+
+        let edges = [
+            [CubeFace::U, CubeFace::F],
+            [CubeFace::U, CubeFace::B],
+            [CubeFace::U, CubeFace::L],
+            [CubeFace::U, CubeFace::R],
+            [CubeFace::D, CubeFace::F],
+            [CubeFace::D, CubeFace::B],
+        ]
+        .map(position);
+        let oriented = corners
+            .solve([position([CubeFace::U]), position([CubeFace::D])])
+            .orient(edges);
+        let lse =
+            |goal: GoalMask| EnumerateSolver::new(Arc::new(LSE_MOVES), 20).with_goal(LseGoal(goal));
+        steps.push(lse_step(
+            &mut rubik,
+            "LSE: edge orientation",
+            "Orient the six edges left with <M, U>, bringing the U and D centers back \
+             on the U/D axis.",
+            lse(oriented.clone()),
+        ));
+        steps.push(lse_step(
+            &mut rubik,
+            "LSE: UL and UR",
+            "Place the UL and UR edges, keeping the edges oriented.",
+            lse(oriented.solve([edges[2], edges[3]])),
+        ));
+        steps.push(lse_step(
+            &mut rubik,
+            "LSE: edge permutation",
+            "Permute the M slice edges and adjust U and M to finish.",
+            EnumerateSolver::new(Arc::new(LSE_MOVES), 20),
+        ));
+        steps
+    }
+}
+
+impl RubikSolver for RouxSolver {
+    fn solve(&self, rubik: Rubik) -> RubikSolveState {
+        let steps = self.steps(rubik.clone());
+        collect_steps(rubik, Arc::new(HTM), &steps)
+    }
+}
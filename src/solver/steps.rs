@@ -0,0 +1,332 @@
+//! Building blocks of human-style solvers: named algorithms, annotated steps
+//! and a search over algorithm applications.
+use crate::{
+    cube::CubeFace,
+    permutation::CubePermutation,
+    prelude::{PtrRotate, RubikLayerTransform},
+    Rubik, RubikLayer,
+};
+
+use super::RubikSolveState;
+
+/// A named move sequence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Alg {
+    pub name: &'static str,
+    pub moves: &'static [&'static RubikLayerTransform],
+}
+
+impl Alg {
+    pub const fn new(name: &'static str, moves: &'static [&'static RubikLayerTransform]) -> Self {
+        Self { name, moves }
+    }
+
+    pub fn notation(&self) -> String {
+        notation(self.moves.iter().copied())
+    }
+}
+
+/// moves separated by spaces, as `R U R' U'`
+pub fn notation<'a>(ops: impl IntoIterator<Item = &'a RubikLayerTransform>) -> String {
+    ops.into_iter()
+        .map(|op| op.to_string())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// the moves undoing `ops`
+pub fn invert(ops: &[&'static RubikLayerTransform]) -> Vec<&'static RubikLayerTransform> {
+    ops.iter()
+        .rev()
+        .map(|op| RubikLayerTransform::from_layer(op.layer(), op.ptr_rotate().inverse()))
+        .collect()
+}
+
+/// How the moves of a sequence are counted, turns of the whole cube being free.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Metric {
+    /// half turn metric: any turn of an outer layer counts one, slice turns count two
+    Htm,
+    /// quarter turn metric: as [`Metric::Htm`] with half turns counting two
+    Qtm,
+    /// slice turn metric: any turn of adjacent layers, slices included, counts one
+    Stm,
+}
+
+impl Metric {
+    /// the moves of `ops` once cancelled, turns of parallel layers played together
+    /// counted as the fewest moves doing the same up to a rotation, so that `r` counts
+    /// one in every metric and `R L'` counts one slice turn
+    pub fn count(self, ops: &[&'static RubikLayerTransform]) -> usize {
+        let ops = RubikLayerTransform::cancel(ops.iter().copied());
+        let mut count = 0;
+        let mut index = 0;
+        while index < ops.len() {
+            let axis = ops[index].layer().axis();
+            // quarter turns of each layer of the axis, all in the same direction
+            let mut turns = [0_u8; 3];
+            while let Some(op) = ops.get(index).filter(|op| op.layer().axis() == axis) {
+                turns[op.layer().depth() as usize] += quarter_turns(op);
+                index += 1;
+            }
+            count += match self {
+                Metric::Htm | Metric::Qtm => [turns[0], turns[2]]
+                    .into_iter()
+                    .map(|turn| (turn + 4 - turns[1]) % 4)
+                    .map(|turn| match (self, turn) {
+                        (_, 0) => 0,
+                        (Metric::Qtm, 2) => 2,
+                        _ => 1,
+                    })
+                    .sum::<usize>(),
+                Metric::Stm => (0..4)
+                    .map(|rotation| {
+                        turns
+                            .iter()
+                            .filter(|turn| (**turn + 4 - rotation) % 4 != 0)
+                            .count()
+                    })
+                    .min()
+                    .unwrap_or(0),
+            };
+        }
+        count
+    }
+}
+
+/// the clockwise quarter turns of the F, L or U layer doing what `op` does
+pub(crate) fn quarter_turns(op: &RubikLayerTransform) -> u8 {
+    let reference = match op.layer().axis() {
+        0 => RubikLayerTransform::F,
+        1 => RubikLayerTransform::L,
+        _ => RubikLayerTransform::U,
+    };
+    let mut rotation = CubePermutation::UNIT;
+    for turns in 0..4 {
+        if rotation == op.rotation() {
+            return turns;
+        }
+        rotation = rotation.compose(reference.rotation());
+    }
+    unreachable!("a layer turns about its axis")
+}
+
+/// One annotated stage of a solution.
+#[derive(Debug, Clone)]
+pub struct Step {
+    pub name: String,
+    pub explanation: String,
+    pub ops: Vec<&'static RubikLayerTransform>,
+}
+
+impl Step {
+    pub fn notation(&self) -> String {
+        notation(self.ops.iter().copied())
+    }
+}
+
+impl std::fmt::Display for Step {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} ({} moves): {}\n  {}",
+            self.name,
+            self.ops.len(),
+            self.notation(),
+            self.explanation
+        )
+    }
+}
+
+/// the solve state after playing the moves of every step on `rubik`
+pub fn collect_steps(
+    rubik: Rubik,
+    op_set: std::sync::Arc<[&'static RubikLayerTransform]>,
+    steps: &[Step],
+) -> RubikSolveState {
+    steps.iter().flat_map(|step| step.ops.iter().copied()).fold(
+        RubikSolveState::new(rubik, op_set),
+        RubikSolveState::transfer,
+    )
+}
+
+/// `op` as seen after `times` quarter `y` rotations of the whole cube, so that an
+/// algorithm written for the front right slot works on the next slot, going
+/// clockwise seen from above: R becomes B, B becomes L, L becomes F and F becomes R.
+pub fn rotate_y(op: &RubikLayerTransform, times: usize) -> &'static RubikLayerTransform {
+    let mut op = RubikLayerTransform::from_layer(op.layer(), op.ptr_rotate());
+    for _ in 0..times % 4 {
+        let layer = op.layer();
+        // `S` turns like `B`, so `M` (turning like `L`) becomes `S'` (turning like `F`)
+        let (layer, ptr_rotate) = if layer == &RubikLayer::R {
+            (&RubikLayer::B, op.ptr_rotate())
+        } else if layer == &RubikLayer::B {
+            (&RubikLayer::L, op.ptr_rotate())
+        } else if layer == &RubikLayer::L {
+            (&RubikLayer::F, op.ptr_rotate())
+        } else if layer == &RubikLayer::F {
+            (&RubikLayer::R, op.ptr_rotate())
+        } else if layer == &RubikLayer::M {
+            (&RubikLayer::S, op.ptr_rotate().inverse())
+        } else if layer == &RubikLayer::S {
+            (&RubikLayer::M, op.ptr_rotate())
+        } else {
+            (layer, op.ptr_rotate())
+        };
+        op = RubikLayerTransform::from_layer(layer, ptr_rotate);
+    }
+    op
+}
+
+/// A candidate move sequence in a [`search`], usually an algorithm after some setup.
+#[derive(Debug, Clone)]
+pub struct Macro {
+    pub name: String,
+    pub ops: Vec<&'static RubikLayerTransform>,
+}
+
+impl Macro {
+    pub fn new(name: impl Into<String>, ops: Vec<&'static RubikLayerTransform>) -> Self {
+        Self {
+            name: name.into(),
+            ops: RubikLayerTransform::cancel(ops),
+        }
+    }
+
+    /// `alg` preceded by each of the four U adjustments
+    pub fn with_auf(alg: &Alg) -> Vec<Self> {
+        Self::with_setup(
+            CubeFace::U,
+            &format!("{} ({})", alg.name, alg.notation()),
+            alg.moves,
+        )
+    }
+
+    /// `ops` preceded by each of the four turns of `face`
+    pub fn with_setup(
+        face: CubeFace,
+        name: &str,
+        ops: &[&'static RubikLayerTransform],
+    ) -> Vec<Self> {
+        [
+            PtrRotate::Rotate0,
+            PtrRotate::Rotate1,
+            PtrRotate::Rotate2,
+            PtrRotate::Rotate3,
+        ]
+        .into_iter()
+        .map(|ptr_rotate| {
+            let setup = RubikLayerTransform::face_turn(face, ptr_rotate);
+            let mut setup_ops = vec![setup];
+            setup_ops.extend(ops.iter().copied());
+            let name = if ptr_rotate == PtrRotate::Rotate0 {
+                name.to_string()
+            } else {
+                format!("{setup} then {name}")
+            };
+            Self::new(name, setup_ops)
+        })
+        .collect()
+    }
+
+    /// `alg` as seen from each of the four sides, see [`rotate_y`]
+    pub fn rotations_y(alg: &Alg) -> Vec<Self> {
+        (0..4)
+            .map(|times| {
+                let ops = alg
+                    .moves
+                    .iter()
+                    .map(|op| rotate_y(op, times))
+                    .collect::<Vec<_>>();
+                Self::new(
+                    format!("{} ({})", alg.name, notation(ops.iter().copied())),
+                    ops,
+                )
+            })
+            .collect()
+    }
+
+    pub fn apply_on(&self, rubik: &mut Rubik) {
+        for op in &self.ops {
+            op.apply_on(rubik);
+        }
+    }
+}
+
+/// The shortest (in macro count, then in moves) chain of at most `max_depth` macros reaching `goal`.
+pub fn search<'m>(
+    rubik: &Rubik,
+    macros: &'m [Macro],
+    max_depth: usize,
+    goal: impl Fn(&Rubik) -> bool,
+) -> Option<Vec<&'m Macro>> {
+    fn dfs<'m>(
+        rubik: &Rubik,
+        macros: &'m [Macro],
+        depth: usize,
+        goal: &impl Fn(&Rubik) -> bool,
+        path: &mut Vec<&'m Macro>,
+        best: &mut Option<(usize, Vec<&'m Macro>)>,
+    ) {
+        if depth == 0 {
+            if goal(rubik) {
+                let len = path.iter().map(|m| m.ops.len()).sum::<usize>();
+                if best.as_ref().is_none_or(|(best_len, _)| len < *best_len) {
+                    *best = Some((len, path.clone()));
+                }
+            }
+            return;
+        }
+        for m in macros {
+            let mut next = rubik.clone();
+            m.apply_on(&mut next);
+            path.push(m);
+            dfs(&next, macros, depth - 1, goal, path, best);
+            path.pop();
+        }
+    }
+    if goal(rubik) {
+        return Some(vec![]);
+    }
+    for depth in 1..=max_depth {
+        let mut best = None;
+        dfs(rubik, macros, depth, &goal, &mut vec![], &mut best);
+        if let Some((_, path)) = best {
+            return Some(path);
+        }
+    }
+    None
+}
+
+/// play the macros found by a [`search`] on `rubik` as one step,
+/// panics if no chain of at most `max_depth` macros reaches `goal`
+pub fn macro_step(
+    rubik: &mut Rubik,
+    name: String,
+    explanation: &str,
+    macros: &[Macro],
+    max_depth: usize,
+    goal: impl Fn(&Rubik) -> bool,
+) -> Step {
+    let found =
+        search(rubik, macros, max_depth, goal).expect("no chain of macros reaches the goal");
+    let ops = RubikLayerTransform::cancel(found.iter().flat_map(|m| m.ops.iter().copied()));
+    for op in &ops {
+        op.apply_on(rubik);
+    }
+    let used = found
+        .iter()
+        .map(|m| m.name.as_str())
+        .collect::<Vec<_>>()
+        .join(", then ");
+    let explanation = if used.is_empty() {
+        format!("{explanation} Already done.")
+    } else {
+        format!("{explanation} Here: {used}.")
+    };
+    Step {
+        name,
+        explanation,
+        ops,
+    }
+}
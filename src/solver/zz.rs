@@ -0,0 +1,56 @@
+//! ZZ inspection: orienting every edge on the F/B axis while placing a line or a
+//! cross on D, after which the first two layers only need `<R, U, L>`.
+use crate::{cube::CubeFace, prelude::RubikLayerTransform as T, Rubik};
+
+use super::{
+    enumerate::Solutions,
+    mask::{GoalMask, MaskSolver},
+    RubikMultiSolver, RubikSolveState, RubikSolver,
+};
+
+/// the moves of the first two layers after EOLine, keeping every edge oriented
+pub const RUL_MOVES: [&T; 9] = [
+    &T::R,
+    &T::R2,
+    &T::RI,
+    &T::U,
+    &T::U2,
+    &T::UI,
+    &T::L,
+    &T::L2,
+    &T::LI,
+];
+
+/// Finds optimal EOLines: every edge oriented on the F/B axis with DF and DB solved.
+pub struct EoLineSolver;
+
+/// Finds optimal EOCrosses: every edge oriented on the F/B axis with the D cross solved.
+pub struct EoCrossSolver;
+
+impl RubikSolver for EoLineSolver {
+    fn solve(&self, rubik: Rubik) -> RubikSolveState {
+        MaskSolver::htm(&GoalMask::eo_line(CubeFace::D)).solve(rubik)
+    }
+}
+
+impl RubikMultiSolver for EoLineSolver {
+    type Solutions = Solutions;
+
+    fn solutions(&self, rubik: Rubik) -> Self::Solutions {
+        MaskSolver::htm(&GoalMask::eo_line(CubeFace::D)).solutions(rubik)
+    }
+}
+
+impl RubikSolver for EoCrossSolver {
+    fn solve(&self, rubik: Rubik) -> RubikSolveState {
+        MaskSolver::htm(&GoalMask::eo_cross(CubeFace::D)).solve(rubik)
+    }
+}
+
+impl RubikMultiSolver for EoCrossSolver {
+    type Solutions = Solutions;
+
+    fn solutions(&self, rubik: Rubik) -> Self::Solutions {
+        MaskSolver::htm(&GoalMask::eo_cross(CubeFace::D)).solutions(rubik)
+    }
+}
@@ -0,0 +1,428 @@
+//! Binary tables (pruning tables, move tables, ...) cached on disk.
+//!
+//! A table file is a fixed size [`TableHeader`] followed by the raw table bytes.
+//! The header records the file format version, the cube model version and the
+//! table's own version together with a checksum of the payload, so a stale or
+//! corrupted file is detected and regenerated instead of silently used.
+use std::{
+    collections::VecDeque,
+    fmt::Display,
+    fs,
+    io::{self, Read, Write},
+    ops::Deref,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+/// version of the on-disk layout of a table file
+pub const FORMAT_VERSION: u16 = 1;
+/// version of the cube model (slot layout of [`crate::Rubik`], move definitions),
+/// bump it whenever a change invalidates tables derived from the model
+pub const MODEL_VERSION: u16 = 1;
+
+const MAGIC: [u8; 4] = *b"RBKT";
+pub const HEADER_LEN: usize = 32;
+
+/// A table that can be generated from scratch and cached on disk.
+pub trait TableSpec {
+    /// file name (without extension) of the table inside the cache directory
+    const NAME: &'static str;
+    /// version of the table content, bump it when [`TableSpec::generate`] changes
+    const VERSION: u32;
+    fn generate() -> Vec<u8>;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TableHeader {
+    pub format_version: u16,
+    pub model_version: u16,
+    pub table_version: u32,
+    pub len: u64,
+    pub checksum: u64,
+}
+
+impl TableHeader {
+    pub fn new<S: TableSpec>(data: &[u8]) -> Self {
+        Self {
+            format_version: FORMAT_VERSION,
+            model_version: MODEL_VERSION,
+            table_version: S::VERSION,
+            len: data.len() as u64,
+            checksum: checksum(data),
+        }
+    }
+
+    pub fn to_bytes(&self) -> [u8; HEADER_LEN] {
+        let mut bytes = [0; HEADER_LEN];
+        bytes[0..4].copy_from_slice(&MAGIC);
+        bytes[4..6].copy_from_slice(&self.format_version.to_le_bytes());
+        bytes[6..8].copy_from_slice(&self.model_version.to_le_bytes());
+        bytes[8..12].copy_from_slice(&self.table_version.to_le_bytes());
+        bytes[12..20].copy_from_slice(&self.len.to_le_bytes());
+        bytes[20..28].copy_from_slice(&self.checksum.to_le_bytes());
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, TableError> {
+        let magic = bytes.len().min(MAGIC.len());
+        if bytes[..magic] != MAGIC[..magic] {
+            return Err(TableError::BadMagic);
+        }
+        if bytes.len() < HEADER_LEN {
+            return Err(TableError::Truncated {
+                found: bytes.len() as u64,
+                expected: HEADER_LEN as u64,
+            });
+        }
+        let u16_at = |i: usize| u16::from_le_bytes([bytes[i], bytes[i + 1]]);
+        let u32_at = |i: usize| u32::from_le_bytes(bytes[i..i + 4].try_into().expect("4 bytes"));
+        let u64_at = |i: usize| u64::from_le_bytes(bytes[i..i + 8].try_into().expect("8 bytes"));
+        Ok(Self {
+            format_version: u16_at(4),
+            model_version: u16_at(6),
+            table_version: u32_at(8),
+            len: u64_at(12),
+            checksum: u64_at(20),
+        })
+    }
+
+    /// check that a header read from disk describes the current version of `S`
+    pub fn check<S: TableSpec>(&self) -> Result<(), TableError> {
+        if self.format_version != FORMAT_VERSION {
+            return Err(TableError::Stale {
+                field: "format version",
+                found: self.format_version as u32,
+                expected: FORMAT_VERSION as u32,
+            });
+        }
+        if self.model_version != MODEL_VERSION {
+            return Err(TableError::Stale {
+                field: "model version",
+                found: self.model_version as u32,
+                expected: MODEL_VERSION as u32,
+            });
+        }
+        if self.table_version != S::VERSION {
+            return Err(TableError::Stale {
+                field: "table version",
+                found: self.table_version,
+                expected: S::VERSION,
+            });
+        }
+        Ok(())
+    }
+}
+
+/// FNV-1a 64 bit hash of the table payload
+pub fn checksum(data: &[u8]) -> u64 {
+    data.iter().fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+        (hash ^ *byte as u64).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+#[derive(Debug)]
+pub enum TableError {
+    Io(io::Error),
+    BadMagic,
+    Stale {
+        field: &'static str,
+        found: u32,
+        expected: u32,
+    },
+    Truncated {
+        found: u64,
+        expected: u64,
+    },
+    Checksum {
+        found: u64,
+        expected: u64,
+    },
+    /// a state of a [`pruning_table`] lies further than a byte can tell
+    TooDeep,
+}
+
+impl Display for TableError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TableError::Io(e) => write!(f, "io error: {e}"),
+            TableError::BadMagic => write!(f, "not a table file"),
+            TableError::Stale {
+                field,
+                found,
+                expected,
+            } => write!(f, "stale table: {field} is {found}, expected {expected}"),
+            TableError::Truncated { found, expected } => {
+                write!(f, "table has {found} bytes, expected {expected}")
+            }
+            TableError::Checksum { found, expected } => {
+                write!(
+                    f,
+                    "checksum mismatch: {found:016x}, expected {expected:016x}"
+                )
+            }
+            TableError::TooDeep => write!(
+                f,
+                "a state is {} moves or more away, too deep for the table",
+                u8::MAX
+            ),
+        }
+    }
+}
+
+impl std::error::Error for TableError {}
+
+impl From<io::Error> for TableError {
+    fn from(e: io::Error) -> Self {
+        TableError::Io(e)
+    }
+}
+
+#[derive(Clone)]
+enum TableData {
+    Owned(Arc<[u8]>),
+    #[cfg(feature = "mmap")]
+    Mapped(Arc<memmap2::Mmap>),
+}
+
+/// A loaded table, cheap to clone and shared read-only between its clones.
+#[derive(Clone)]
+pub struct Table {
+    header: TableHeader,
+    data: TableData,
+}
+
+impl Deref for Table {
+    type Target = [u8];
+    fn deref(&self) -> &Self::Target {
+        match &self.data {
+            TableData::Owned(data) => data,
+            #[cfg(feature = "mmap")]
+            TableData::Mapped(map) => &map[HEADER_LEN..],
+        }
+    }
+}
+
+impl std::fmt::Debug for Table {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Table")
+            .field("header", &self.header)
+            .field("mapped", &self.is_mapped())
+            .finish()
+    }
+}
+
+impl Table {
+    pub fn generate<S: TableSpec>() -> Self {
+        Self::from_vec::<S>(S::generate())
+    }
+
+    pub fn from_vec<S: TableSpec>(data: Vec<u8>) -> Self {
+        Self {
+            header: TableHeader::new::<S>(&data),
+            data: TableData::Owned(data.into()),
+        }
+    }
+
+    pub fn header(&self) -> &TableHeader {
+        &self.header
+    }
+
+    pub fn is_mapped(&self) -> bool {
+        match self.data {
+            TableData::Owned(_) => false,
+            #[cfg(feature = "mmap")]
+            TableData::Mapped(_) => true,
+        }
+    }
+
+    /// recompute the checksum of the payload and compare it with the header
+    pub fn verify(&self) -> Result<(), TableError> {
+        if self.len() as u64 != self.header.len {
+            return Err(TableError::Truncated {
+                found: self.len() as u64,
+                expected: self.header.len,
+            });
+        }
+        let found = checksum(self);
+        if found != self.header.checksum {
+            return Err(TableError::Checksum {
+                found,
+                expected: self.header.checksum,
+            });
+        }
+        Ok(())
+    }
+
+    /// write the table to `path`, through a temporary file renamed in place so that
+    /// concurrent readers never observe a half written table
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let path = path.as_ref();
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let tmp = path.with_extension(format!("tmp{}", std::process::id()));
+        {
+            let mut file = fs::File::create(&tmp)?;
+            file.write_all(&self.header.to_bytes())?;
+            file.write_all(self)?;
+            file.sync_all()?;
+        }
+        fs::rename(tmp, path)
+    }
+
+    /// read a table file into memory, checking its header against `S` and its checksum
+    pub fn load<S: TableSpec>(path: impl AsRef<Path>) -> Result<Self, TableError> {
+        let mut file = fs::File::open(path)?;
+        let mut header = Vec::with_capacity(HEADER_LEN);
+        (&mut file)
+            .take(HEADER_LEN as u64)
+            .read_to_end(&mut header)?;
+        let header = TableHeader::from_bytes(&header)?;
+        header.check::<S>()?;
+        // the header is not trusted with an allocation before the file backs it
+        let found = file.metadata()?.len().saturating_sub(HEADER_LEN as u64);
+        if found != header.len {
+            return Err(TableError::Truncated {
+                found,
+                expected: header.len,
+            });
+        }
+        let mut data = Vec::with_capacity(header.len as usize);
+        file.read_to_end(&mut data)?;
+        let table = Self {
+            header,
+            data: TableData::Owned(data.into()),
+        };
+        table.verify()?;
+        Ok(table)
+    }
+
+    /// map a table file read-only into memory, checking its header against `S`.
+    ///
+    /// The payload is paged in lazily and shared between processes mapping the same file,
+    /// the checksum is only verified if `verify` is set since that touches every page.
+    #[cfg(feature = "mmap")]
+    pub fn load_mmap<S: TableSpec>(
+        path: impl AsRef<Path>,
+        verify: bool,
+    ) -> Result<Self, TableError> {
+        let file = fs::File::open(path)?;
+        // SAFETY: table files are only ever replaced through a rename in `save`,
+        // never modified in place.
+        let map = unsafe { memmap2::Mmap::map(&file)? };
+        let header = TableHeader::from_bytes(&map)?;
+        header.check::<S>()?;
+        let table = Self {
+            header,
+            data: TableData::Mapped(Arc::new(map)),
+        };
+        if table.len() as u64 != header.len {
+            return Err(TableError::Truncated {
+                found: table.len() as u64,
+                expected: header.len,
+            });
+        }
+        if verify {
+            table.verify()?;
+        }
+        Ok(table)
+    }
+}
+
+/// A directory of cached tables.
+#[derive(Debug, Clone)]
+pub struct TableCache {
+    pub dir: PathBuf,
+    /// map tables instead of reading them, requires the `mmap` feature
+    pub mmap: bool,
+    /// verify checksums of mapped tables on load, read tables are always verified
+    pub verify: bool,
+}
+
+impl TableCache {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            mmap: false,
+            verify: true,
+        }
+    }
+
+    /// `$RUBIK_TABLE_DIR` if set, a `rubik-tables` directory in the system temp dir otherwise
+    pub fn default_dir() -> PathBuf {
+        std::env::var_os("RUBIK_TABLE_DIR")
+            .map(PathBuf::from)
+            .unwrap_or_else(|| std::env::temp_dir().join("rubik-tables"))
+    }
+
+    pub fn path_of<S: TableSpec>(&self) -> PathBuf {
+        self.dir.join(format!("{}.tbl", S::NAME))
+    }
+
+    pub fn load<S: TableSpec>(&self) -> Result<Table, TableError> {
+        let path = self.path_of::<S>();
+        #[cfg(feature = "mmap")]
+        if self.mmap {
+            return Table::load_mmap::<S>(path, self.verify);
+        }
+        Table::load::<S>(path)
+    }
+
+    /// load the cached table, regenerating and saving it if it is missing, stale or corrupted.
+    ///
+    /// A table that cannot be saved, as in a read-only directory, is still returned.
+    pub fn load_or_generate<S: TableSpec>(&self) -> Table {
+        self.load::<S>()
+            .unwrap_or_else(|_| self.regenerate::<S>().0)
+    }
+
+    /// generate the table and save it, returning it along with the error of saving it
+    pub fn regenerate<S: TableSpec>(&self) -> (Table, Result<(), TableError>) {
+        let table = Table::generate::<S>();
+        match table.save(self.path_of::<S>()) {
+            Ok(()) => (self.load::<S>().unwrap_or(table), Ok(())),
+            Err(e) => (table, Err(e.into())),
+        }
+    }
+}
+
+impl Default for TableCache {
+    fn default() -> Self {
+        Self::new(Self::default_dir())
+    }
+}
+
+/// Breadth first search over a coordinate space of `size` states, storing the distance
+/// of every state to the nearest of `start` (`u8::MAX` for unreachable states).
+///
+/// `neighbors` lists the coordinates one move away from a coordinate. A state
+/// `u8::MAX` moves or more away cannot be told from an unreachable one, and fails
+/// with [`TableError::TooDeep`].
+pub fn pruning_table<I>(
+    size: usize,
+    start: impl IntoIterator<Item = usize>,
+    neighbors: impl Fn(usize) -> I,
+) -> Result<Vec<u8>, TableError>
+where
+    I: IntoIterator<Item = usize>,
+{
+    let mut table = vec![u8::MAX; size];
+    let mut quene = VecDeque::new();
+    for coord in start {
+        table[coord] = 0;
+        quene.push_back(coord);
+    }
+    while let Some(coord) = quene.pop_front() {
+        let depth = table[coord];
+        for next in neighbors(coord) {
+            if table[next] == u8::MAX {
+                if depth + 1 == u8::MAX {
+                    return Err(TableError::TooDeep);
+                }
+                table[next] = depth + 1;
+                quene.push_back(next);
+            }
+        }
+    }
+    Ok(table)
+}
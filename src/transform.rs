@@ -66,6 +66,19 @@ impl RubikTransform {
     pub fn repeat(self, n: usize) -> Self {
         Self::Repeat(Box::new(self), n)
     }
+
+    /// the layer moves played in order, repetitions unrolled
+    pub fn ops(&self) -> Vec<&'static RubikLayerTransform> {
+        match self {
+            RubikTransform::Layer(layer) => {
+                vec![RubikLayerTransform::from_layer(layer.layer(), layer.ptr_rotate())]
+            }
+            RubikTransform::Repeat(transform, times) => transform.ops().repeat(*times),
+            RubikTransform::Combine(transforms) => {
+                transforms.iter().flat_map(RubikTransform::ops).collect()
+            }
+        }
+    }
 }
 
 impl<T> From<Vec<T>> for RubikTransform
@@ -1,5 +1,6 @@
 use std::fmt::{Display, Formatter};
 
+use crate::cube::CubeFace;
 use crate::permutation::CubePermutation;
 use crate::{CubePosition, Rubik, RubikLayer};
 
@@ -44,6 +45,36 @@ impl RubikLayerTransform {
         }
         s
     }
+    /// merge adjacent moves of the same layer and drop those cancelling out,
+    /// looking past moves of parallel layers since they commute
+    pub fn cancel<'a>(seq: impl IntoIterator<Item = &'a Self>) -> Vec<&'static Self> {
+        let mut output: Vec<&'static Self> = vec![];
+        for op in seq {
+            if op.ptr_rotate == PtrRotate::Rotate0 {
+                continue;
+            }
+            let same_layer = output
+                .iter()
+                .rev()
+                .take_while(|prev| prev.layer.axis() == op.layer.axis())
+                .position(|prev| prev.layer == op.layer)
+                .map(|back| output.len() - 1 - back);
+            match same_layer {
+                Some(idx) => {
+                    let merged = output[idx]
+                        .merge(*op)
+                        .expect("moves of the same layer merge");
+                    if merged.ptr_rotate == PtrRotate::Rotate0 {
+                        output.remove(idx);
+                    } else {
+                        output[idx] = Self::from_layer(merged.layer, merged.ptr_rotate);
+                    }
+                }
+                None => output.push(Self::from_layer(op.layer, op.ptr_rotate)),
+            }
+        }
+        output
+    }
     pub fn apply_on(&self, rubik: &mut Rubik) {
         for index in self.layer.iter().copied() {
             rubik.cubes[index as usize].rotate(self.rotation);
@@ -77,6 +108,13 @@ impl RubikLayerTransform {
         }
         None
     }
+    /// where the cube at `position` goes, while [`RubikLayerTransform::apply_on_position`]
+    /// tells where the cube landing on `position` comes from
+    pub fn destination(&self, position: CubePosition) -> CubePosition {
+        self.inverse()
+            .apply_on_position(position)
+            .unwrap_or(position)
+    }
     pub const fn inverse(self) -> Self {
         Self {
             layer: self.layer,
@@ -91,6 +129,54 @@ impl RubikLayerTransform {
             ptr_rotate: self.ptr_rotate.square(),
         }
     }
+    /// the single move doing `self` then `next`, if both turn the same layer
+    pub const fn merge(self, next: Self) -> Option<Self> {
+        if self.layer.cude_indexes[0] != next.layer.cude_indexes[0]
+            || self.layer.cude_indexes[8] != next.layer.cude_indexes[8]
+        {
+            return None;
+        }
+        Some(Self {
+            layer: self.layer,
+            rotation: self.rotation.compose(next.rotation),
+            ptr_rotate: self.ptr_rotate.compose(next.ptr_rotate),
+        })
+    }
+    /// the move turning `layer` by `ptr_rotate`, [`RubikLayerTransform::UNIT`] for `Rotate0`
+    pub fn from_layer(layer: &RubikLayer, ptr_rotate: PtrRotate) -> &'static Self {
+        if ptr_rotate == PtrRotate::Rotate0 {
+            return &Self::UNIT;
+        }
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|op| op.layer == layer && op.ptr_rotate == ptr_rotate)
+            .expect("every layer has a move for each rotation")
+    }
+    /// the move turning `face` by `ptr_rotate`
+    pub fn face_turn(face: CubeFace, ptr_rotate: PtrRotate) -> &'static Self {
+        let layer = match face {
+            CubeFace::F => &RubikLayer::F,
+            CubeFace::B => &RubikLayer::B,
+            CubeFace::R => &RubikLayer::R,
+            CubeFace::L => &RubikLayer::L,
+            CubeFace::U => &RubikLayer::U,
+            CubeFace::D => &RubikLayer::D,
+        };
+        Self::from_layer(layer, ptr_rotate)
+    }
+    /// the outer face turned by the move, `None` for slice moves
+    pub fn face(&self) -> Option<CubeFace> {
+        match self.layer.marker() {
+            "F" => Some(CubeFace::F),
+            "B" => Some(CubeFace::B),
+            "R" => Some(CubeFace::R),
+            "L" => Some(CubeFace::L),
+            "U" => Some(CubeFace::U),
+            "D" => Some(CubeFace::D),
+            _ => None,
+        }
+    }
     pub const fn rotation(&self) -> CubePermutation {
         self.rotation
     }
@@ -160,6 +246,37 @@ impl RubikLayerTransform {
     pub const EI: Self = Self::E.inverse();
     pub const SI: Self = Self::S.inverse();
 
+    /// every quarter and half turn of every layer
+    pub const ALL: [&'static Self; 27] = [
+        &Self::F,
+        &Self::F2,
+        &Self::FI,
+        &Self::B,
+        &Self::B2,
+        &Self::BI,
+        &Self::L,
+        &Self::L2,
+        &Self::LI,
+        &Self::R,
+        &Self::R2,
+        &Self::RI,
+        &Self::U,
+        &Self::U2,
+        &Self::UI,
+        &Self::D,
+        &Self::D2,
+        &Self::DI,
+        &Self::M,
+        &Self::M2,
+        &Self::MI,
+        &Self::E,
+        &Self::E2,
+        &Self::EI,
+        &Self::S,
+        &Self::S2,
+        &Self::SI,
+    ];
+
     pub const F2: Self = Self::F.square();
     pub const B2: Self = Self::B.square();
     pub const L2: Self = Self::L.square();
@@ -204,6 +321,14 @@ impl PtrRotate {
             PtrRotate::Rotate3 => PtrRotate::Rotate2,
         }
     }
+    pub const fn compose(self, next: Self) -> Self {
+        match (self as u8 + next as u8) % 4 {
+            0 => PtrRotate::Rotate0,
+            1 => PtrRotate::Rotate1,
+            2 => PtrRotate::Rotate2,
+            _ => PtrRotate::Rotate3,
+        }
+    }
     pub const fn as_permutation(self) -> &'static [usize; 9] {
         match self {
             PtrRotate::Rotate0 => &[0, 1, 2, 3, 4, 5, 6, 7, 8],
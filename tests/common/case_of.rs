@@ -0,0 +1,15 @@
+use rubik::{
+    prelude::*,
+    solver::steps::{invert, Alg},
+};
+
+/// the case an algorithm solves, seen after a pre-AUF
+#[allow(dead_code)]
+pub fn case_of(alg: &Alg, pre_auf: &'static RubikLayerTransform) -> Rubik {
+    let mut rubik = Rubik::new();
+    for op in invert(alg.moves) {
+        op.apply_on(&mut rubik);
+    }
+    pre_auf.inverse().apply_on(&mut rubik);
+    rubik
+}
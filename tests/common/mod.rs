@@ -0,0 +1,2 @@
+pub mod case_of;
+pub mod scrambled;
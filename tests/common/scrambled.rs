@@ -0,0 +1,9 @@
+use rubik::prelude::*;
+
+/// the state `transform` leaves a solved cube in
+#[allow(dead_code)]
+pub fn scrambled(transform: RubikTransform) -> Rubik {
+    let mut rubik = Rubik::new();
+    rubik.execute(&transform);
+    rubik
+}
@@ -0,0 +1,140 @@
+use rubik::{prelude::*, solver::steps::Metric};
+
+#[test]
+fn test_metric() {
+    use RubikLayerTransform as T;
+    let count = |ops: &[&'static T]| [Metric::Htm, Metric::Qtm, Metric::Stm].map(|m| m.count(ops));
+    assert_eq!(count(&[&T::R, &T::U, &T::RI, &T::UI]), [4, 4, 4]);
+    assert_eq!(count(&[&T::R2, &T::U]), [2, 3, 2]);
+    // r, a slice and a rotation
+    assert_eq!(count(&[&T::R, &T::MI]), [1, 1, 1]);
+    assert_eq!(count(&[&T::M]), [2, 2, 1]);
+    assert_eq!(count(&[&T::R, &T::MI, &T::LI]), [0, 0, 0]);
+    assert_eq!(count(&[&T::M2, &T::U, &T::M2]), [5, 9, 3]);
+    // R L' is a slice turn and a rotation, cancelled moves are free
+    assert_eq!(count(&[&T::R, &T::LI, &T::U, &T::UI]), [2, 2, 1]);
+}
+
+#[cfg(feature = "singmaster")]
+mod algdb {
+    use rubik::{
+        algdb::{AlgDb, AlgDbError},
+        prelude::*,
+        solver::{
+            last_layer::{OLL, PLL},
+            steps::{invert, Metric},
+        },
+    };
+
+    const DB: &str = "
+# a few algorithms
+[PLL]
+case T perm
+  alg R U R' U' R' F R2 U' R' U' R U R' F'
+  alg (R U R' U') (R' F R2 U') (R' U' R U R' F')
+  tags 3-gen, popular
+
+case PLL Ua
+  alg M2 U M U2 M' U M2
+  alg R U' R U R U R U' R' U' R2
+
+[OLL]
+case OLL 27 / Sune
+  alg R U R' U R U2 R'
+";
+
+    #[test]
+    fn test_parse() {
+        let db = AlgDb::parse(DB).unwrap();
+        assert_eq!(db.sets.len(), 2);
+        let t_perm = db.case("PLL", "T perm").expect("a T perm");
+        assert_eq!(t_perm.algs.len(), 2);
+        assert_eq!(t_perm.algs[0].moves, t_perm.algs[1].moves);
+        assert_eq!(t_perm.tags, ["3-gen", "popular"]);
+        assert!(db.verify().is_empty(), "{:?}", db.verify());
+
+        assert_eq!(
+            AlgDb::parse("case T perm").unwrap_err(),
+            AlgDbError::Syntax {
+                line: 1,
+                message: "expected a [set] first".to_string()
+            }
+        );
+        assert!(matches!(
+            AlgDb::parse("[PLL]\nalg R U"),
+            Err(AlgDbError::Syntax { line: 2, .. })
+        ));
+        assert!(matches!(
+            AlgDb::parse("[PLL]\ncase T\n  alg R Q"),
+            Err(AlgDbError::Syntax { line: 3, .. })
+        ));
+    }
+
+    #[test]
+    fn test_verify() {
+        let db = AlgDb::parse(
+            "[PLL]
+case T perm
+  alg R U R' U' R' F R2 U' R' U' R U R' F'
+  alg R U R' U R U2 R'
+  alg R U R'
+case Y perm
+  alg R U R' U' R' F R2 U' R' U' R U R' F'",
+        )
+        .unwrap();
+        let errors = db.verify();
+        assert_eq!(errors.len(), 3);
+        assert!(
+            matches!(&errors[0], AlgDbError::Mismatch { alg, .. } if alg == "R U R' U R U2 R'")
+        );
+        assert!(matches!(&errors[1], AlgDbError::BreaksF2l { alg, .. } if alg == "R U R'"));
+        assert!(matches!(&errors[2], AlgDbError::Misnamed { case, .. } if case == "Y perm"));
+
+        // the built in sets, written out
+        let mut src = String::new();
+        for (set, algs) in [("OLL", &OLL[..]), ("PLL", &PLL[..])] {
+            src.push_str(&format!("[{set}]\n"));
+            for alg in algs {
+                src.push_str(&format!("case {}\n  alg {}\n", alg.name, alg.notation()));
+            }
+        }
+        let db = AlgDb::parse(&src).unwrap();
+        assert!(db.verify().is_empty(), "{:?}", db.verify());
+    }
+
+    #[test]
+    fn test_lookup() {
+        let db = AlgDb::parse(DB).unwrap();
+        let ua = db.case("PLL", "PLL Ua").expect("a Ua perm");
+        assert_eq!(
+            db.best("PLL", "PLL Ua", Metric::Qtm)
+                .map(|alg| alg.notation.as_str()),
+            Some(ua.algs[1].notation.as_str())
+        );
+        assert_eq!(
+            db.best("PLL", "PLL Ua", Metric::Stm)
+                .map(|alg| alg.notation.as_str()),
+            Some(ua.algs[0].notation.as_str())
+        );
+
+        let mut rubik = Rubik::new();
+        RubikLayerTransform::U.apply_on(&mut rubik);
+        for op in invert(&db.case("PLL", "T perm").unwrap().algs[0].moves) {
+            op.apply_on(&mut rubik);
+        }
+        let found = db.by_effect(&rubik);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].1.name, "T perm");
+
+        let path = std::env::temp_dir().join("rubik_test_algdb.txt");
+        std::fs::write(&path, DB).unwrap();
+        assert_eq!(AlgDb::load(&path).unwrap().sets.len(), 2);
+        std::fs::write(
+            &path,
+            "[PLL]\ncase Y perm\n  alg R U R' U' R' F R2 U' R' U' R U R' F'",
+        )
+        .unwrap();
+        assert!(AlgDb::load(&path).is_err());
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,47 @@
+use rubik::{
+    prelude::*,
+    solver::{beginner::BeginnerSolver, RubikSolver},
+    tf,
+};
+
+mod common;
+use common::scrambled::scrambled;
+
+#[test]
+fn test_beginner_solves() {
+    let mut scrambles = vec![
+        Rubik::new(),
+        scrambled(tf!(R, U, RI, UI, F2, D, LI, B, R2, UI)),
+    ];
+    for _ in 0..3 {
+        let mut rubik = Rubik::new();
+        rubik.shuffle(25);
+        scrambles.push(rubik);
+    }
+    for rubik in scrambles {
+        let steps = BeginnerSolver.steps(rubik.clone());
+        assert!(steps
+            .iter()
+            .all(|step| !step.name.is_empty() && !step.explanation.is_empty()));
+        let (solved, ops) = BeginnerSolver.solve(rubik.clone()).collect();
+        assert!(solved.is_solved());
+        assert_eq!(
+            ops.len(),
+            steps.iter().map(|step| step.ops.len()).sum::<usize>()
+        );
+        let mut replay = rubik;
+        for op in ops {
+            op.apply_on(&mut replay);
+        }
+        assert!(replay.is_solved());
+    }
+}
+
+#[test]
+fn test_beginner_solved_cube() {
+    let steps = BeginnerSolver.steps(Rubik::new());
+    assert!(steps.iter().all(|step| step.ops.is_empty()));
+    assert!(steps
+        .iter()
+        .any(|step| step.explanation.ends_with("Already done.")));
+}
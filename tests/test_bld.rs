@@ -0,0 +1,78 @@
+use rubik::{
+    bld::{Bld, LetterScheme},
+    prelude::*,
+    solver::last_layer::PLL,
+    tf,
+};
+
+mod common;
+use common::scrambled::scrambled;
+
+fn t_perm() -> Rubik {
+    let mut rubik = Rubik::new();
+    let alg = PLL.iter().find(|alg| alg.name == "T perm").expect("T perm");
+    alg.moves.iter().for_each(|op| op.apply_on(&mut rubik));
+    rubik
+}
+
+#[test]
+fn test_memo() {
+    let memo = Bld::default().memo(&Rubik::new());
+    assert!(memo.corners.targets.is_empty() && memo.edges.targets.is_empty());
+    assert!(!memo.parity);
+
+    // a U turn cycles UFR, UBR, UBL and UFL
+    let memo = Bld::default().memo(&scrambled(tf!(U)));
+    assert_eq!(memo.corners.targets, ['B', 'A', 'D']);
+    assert_eq!(memo.edges.targets, ['B', 'A', 'D']);
+    assert!(memo.parity);
+
+    let memo = Bld::default().memo(&scrambled(tf!(R, U, RI, D, R, UI, RI, DI)));
+    assert_eq!(memo.corners.pairs(), ["LP"]);
+    assert!(memo.corners.breaks.is_empty() && memo.edges.targets.is_empty());
+    assert!(!memo.parity);
+
+    // the T perm swaps UFR with UBR and UL with UR, the edge buffer being solved
+    let memo = Bld::default().memo(&t_perm());
+    assert_eq!(memo.corners.targets, ['B']);
+    assert_eq!(memo.edges.targets, ['B', 'D', 'B']);
+    assert_eq!(memo.edges.breaks, [0]);
+    assert!(memo.parity);
+    assert_eq!(
+        memo.to_string(),
+        "edges: BD B (cycle breaks: B)\ncorners: B\nparity\n"
+    );
+}
+
+#[test]
+fn test_twists_and_flips() {
+    let memo = Bld::default().memo(&scrambled(tf!(
+        RI, DI, R, D, RI, DI, R, D, U, DI, RI, D, R, DI, RI, D, R, UI
+    )));
+    assert!(memo.corners.targets.is_empty());
+    assert_eq!(
+        memo.corners.in_place,
+        [(CubePosition::BUR, 1), (CubePosition::FUR, 2)]
+    );
+    assert!(memo.to_string().contains("twisted: UBR cw, UFR ccw"));
+
+    let memo = Bld::default().memo(&scrambled(tf!(MI, U, MI, U, MI, U, MI, U)));
+    assert!(memo.edges.targets.is_empty() && memo.corners.targets.is_empty());
+    assert_eq!(memo.edges.in_place.len(), 4);
+    assert!(memo.edges.in_place.iter().all(|(_, flip)| *flip == 1));
+}
+
+#[test]
+fn test_scheme_and_buffers() {
+    let lower = std::array::from_fn(|index| (b'a' + index as u8) as char);
+    let scheme = LetterScheme::new(lower, lower);
+    assert_eq!(scheme.letter(CubePosition::FUR, CubeFace::R), Some('m'));
+    assert_eq!(scheme.letter(CubePosition::DLR, CubeFace::D), Some('v'));
+    assert_eq!(scheme.letter(CubePosition::F, CubeFace::F), None);
+
+    let bld = Bld::new(scheme).with_edge_buffer(CubePosition::UR, CubeFace::U);
+    let memo = bld.memo(&t_perm());
+    assert_eq!(memo.corners.targets, ['b']);
+    assert_eq!(memo.edges.targets, ['d']);
+    assert!(memo.edges.breaks.is_empty());
+}
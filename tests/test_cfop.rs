@@ -0,0 +1,40 @@
+use rubik::{
+    prelude::*,
+    solver::{cfop::CfopSolver, RubikSolver},
+    tf,
+};
+
+#[test]
+fn test_cfop_solves() {
+    let mut rubik = Rubik::new();
+    rubik.execute(&tf!(F, R2, D, BI, L, U2, R, FI));
+    let steps = CfopSolver.steps(rubik.clone());
+    let names = steps
+        .iter()
+        .map(|step| step.name.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(names[0], "cross");
+    assert!(names[1..5].iter().all(|name| name.starts_with("F2L")));
+    assert_eq!(names[5..], ["OLL", "PLL"]);
+    assert!(steps[5].explanation.starts_with("Case OLL"));
+    assert!(steps[6].explanation.starts_with("Case") && steps[6].explanation.contains("perm"));
+    for op in steps.iter().flat_map(|step| step.ops.iter()) {
+        op.apply_on(&mut rubik);
+    }
+    assert!(rubik.is_solved());
+}
+
+#[test]
+fn test_cfop_last_layer() {
+    // T perm
+    let mut rubik = Rubik::new();
+    rubik.execute(&tf!(R, U, RI, UI, RI, F, R2, UI, RI, UI, R, U, RI, FI));
+    let steps = CfopSolver.steps(rubik.clone());
+    assert!(steps[..6].iter().all(|step| step.ops.is_empty()));
+    assert!(steps[5].explanation.starts_with("OLL skip"));
+    assert!(steps[6].explanation.starts_with("Case T perm"));
+    assert_eq!(steps[6].ops.len(), 14);
+    let (solved, ops) = CfopSolver.solve(rubik).collect();
+    assert!(solved.is_solved());
+    assert_eq!(ops.len(), 14);
+}
@@ -0,0 +1,100 @@
+use rubik::{
+    bld::Sticker,
+    prelude::*,
+    solver::{
+        commutator::{Commutator, CommutatorFinder},
+        enumerate::HTM,
+        steps::Metric,
+    },
+    tf,
+};
+
+/// whether the moves of `commutator` bring the piece showing each sticker onto the
+/// next one and leave the other pieces alone
+fn cycles(commutator: &Commutator, stickers: [Sticker; 3]) -> bool {
+    let mut rubik = Rubik::new();
+    commutator
+        .moves()
+        .iter()
+        .for_each(|op| op.apply_on(&mut rubik));
+    CubePosition::all()
+        .filter(|slot| slot.is_corner() || slot.is_edge())
+        .all(|slot| {
+            let cube = rubik
+                .cubes_at([slot as usize].into_iter())
+                .next()
+                .expect("a cube");
+            match (0..3).find(|index| stickers[(index + 1) % 3].0 == slot) {
+                Some(index) => {
+                    let (from, from_face) = stickers[index];
+                    let to_face = stickers[(index + 1) % 3].1;
+                    cube.home(slot) == from && cube.get(to_face) == from_face
+                }
+                None => cube.home(slot) == slot && cube.orientation(slot) == 0,
+            }
+        })
+}
+
+#[test]
+fn test_edge_commutators() {
+    let finder = CommutatorFinder::default();
+    let stickers = [
+        (CubePosition::FU, CubeFace::U),
+        (CubePosition::BU, CubeFace::U),
+        (CubePosition::FD, CubeFace::F),
+    ];
+    let found = finder.find(stickers[0], stickers[1], stickers[2]);
+    assert!(found.iter().all(|commutator| cycles(commutator, stickers)));
+    let best = &found[0];
+    assert!(best.setup.is_empty());
+    assert_eq!(best.move_count(Metric::Stm), 8);
+    assert_eq!(best.interchange, [&RubikLayerTransform::U2]);
+    assert_eq!(best.insertion.len(), 3);
+    assert_eq!(best.to_string(), "[B M' B', U2]");
+
+    // UF to UR to UL needs a setup
+    let stickers = [
+        (CubePosition::FU, CubeFace::U),
+        (CubePosition::UR, CubeFace::U),
+        (CubePosition::UL, CubeFace::U),
+    ];
+    let found = finder.find(stickers[0], stickers[1], stickers[2]);
+    assert!(found.iter().all(|commutator| cycles(commutator, stickers)));
+    assert_eq!(found[0].to_string(), "[F2: [U' M' U, U2]]");
+    assert_eq!(found[0].move_count(Metric::Stm), 7);
+}
+
+#[test]
+fn test_corner_commutators() {
+    let finder = CommutatorFinder::new(&HTM, 2);
+    let stickers = [
+        (CubePosition::FUR, CubeFace::U),
+        (CubePosition::FDR, CubeFace::R),
+        (CubePosition::FDL, CubeFace::F),
+    ];
+    let found = finder.find(stickers[0], stickers[1], stickers[2]);
+    assert!(!found.is_empty());
+    assert!(found.iter().all(|commutator| cycles(commutator, stickers)));
+    assert!(found
+        .windows(2)
+        .all(|pair| pair[0].move_count(Metric::Stm) <= pair[1].move_count(Metric::Stm)));
+    let htm = found
+        .iter()
+        .map(|commutator| commutator.move_count(Metric::Htm));
+    assert_eq!(htm.min(), Some(8));
+    let known = tf!(R, U, RI, D, R, UI, RI, DI);
+    assert!(found
+        .iter()
+        .any(|commutator| commutator.moves() == known.ops()));
+    assert!(found.iter().all(|commutator| commutator.setup.len() <= 2));
+}
+
+#[test]
+#[should_panic]
+fn test_mixed_stickers() {
+    CommutatorFinder::new(&HTM, 0).find(
+        (CubePosition::FUR, CubeFace::U),
+        (CubePosition::FU, CubeFace::U),
+        (CubePosition::FDR, CubeFace::D),
+    );
+}
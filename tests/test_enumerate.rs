@@ -0,0 +1,70 @@
+use std::sync::Arc;
+
+use rubik::{
+    prelude::*,
+    solver::{enumerate::EnumerateSolver, RubikMultiSolver, RubikSolver},
+    tf,
+};
+
+fn solution_strings(scramble: RubikTransform, solver: EnumerateSolver) -> Vec<String> {
+    let mut rubik = Rubik::new();
+    rubik.execute(&scramble);
+    solver
+        .solutions(rubik)
+        .map(|s| {
+            let (rubik, ops) = s.collect();
+            assert!(rubik.is_solved());
+            RubikLayerTransform::sequence_to_string(ops.into_iter())
+        })
+        .collect()
+}
+
+#[test]
+fn test_parallel_moves() {
+    // `L' R'` and `R' L'` are the same solution
+    assert_eq!(
+        solution_strings(tf!(R, L), EnumerateSolver::htm(2)),
+        vec!["L'R'"]
+    );
+    assert_eq!(
+        solution_strings(tf!(R, U2, FI), EnumerateSolver::htm(3)),
+        vec!["FU2R'"]
+    );
+}
+
+#[test]
+fn test_increasing_length() {
+    let solver = EnumerateSolver::new(
+        Arc::new([
+            &RubikLayerTransform::U2,
+            &RubikLayerTransform::D2,
+            &RubikLayerTransform::L2,
+            &RubikLayerTransform::R2,
+        ]),
+        6,
+    );
+    let solutions = solution_strings(tf!(R2, U2), solver);
+    assert_eq!(solutions[..2], ["U2R2", "D2L2R2U2D2L2"]);
+    assert!(solutions.windows(2).all(|w| w[0].len() <= w[1].len()));
+    let unique = solutions.iter().collect::<std::collections::HashSet<_>>();
+    assert_eq!(unique.len(), solutions.len());
+}
+
+#[test]
+fn test_quarter_turns_only() {
+    let solver = EnumerateSolver::new(
+        Arc::new([&RubikLayerTransform::R, &RubikLayerTransform::RI]),
+        3,
+    );
+    // R2 is reached as `R R`, but not again as `R' R'`
+    assert_eq!(solution_strings(tf!(R2), solver), vec!["RR"]);
+}
+
+#[test]
+fn test_solve_too_short() {
+    let mut rubik = Rubik::new();
+    rubik.execute(&tf!(R, U));
+    let (unsolved, ops) = EnumerateSolver::htm(1).solve(rubik.clone()).collect();
+    assert_eq!(unsolved, rubik);
+    assert!(ops.is_empty());
+}
@@ -0,0 +1,113 @@
+use rubik::{
+    prelude::*,
+    solver::{
+        beginner::SUNE,
+        ergonomics::{CostTable, Ergonomics},
+        steps::{invert, rotate_y},
+    },
+};
+
+use RubikLayerTransform as T;
+
+fn solves(case: &[&'static T], alg: &[&'static T]) -> bool {
+    let mut rubik = Rubik::new();
+    invert(case)
+        .iter()
+        .chain(alg)
+        .for_each(|op| op.apply_on(&mut rubik));
+    rubik.is_solved()
+}
+
+#[test]
+fn test_score() {
+    let ergonomics = Ergonomics::default();
+    let costs = CostTable::default();
+    // a whole cube turn, however written
+    assert_eq!(ergonomics.score(&[&T::U, &T::EI, &T::DI]), costs.rotation);
+    // or as a turn of R and a wide turn of L
+    assert_eq!(
+        ergonomics.score(&[&T::R, &T::MI, &T::LI]),
+        2.0 * costs.wrist
+    );
+    assert_eq!(ergonomics.score(&[&T::R, &T::RI]), 0.0);
+    // r is played as a wide R
+    assert_eq!(ergonomics.score(&[&T::R, &T::MI]), costs.wrist);
+    assert_eq!(ergonomics.score(&[&T::M]), costs.slice);
+
+    // turning the wrist up twice needs a regrip
+    let sexy = ergonomics.score(&[&T::R, &T::U, &T::RI, &T::U]);
+    let regrip = ergonomics.score(&[&T::R, &T::U, &T::R, &T::U]);
+    assert_eq!(regrip - sexy, costs.regrip);
+
+    // Sune is faster from the right than from the back
+    let back = SUNE
+        .moves
+        .iter()
+        .map(|op| rotate_y(op, 1))
+        .collect::<Vec<_>>();
+    assert!(ergonomics.score(SUNE.moves) < ergonomics.score(&back));
+    let cheap_back = Ergonomics::new(CostTable {
+        awkward: 0.5,
+        ..costs
+    });
+    assert!(cheap_back.score(SUNE.moves) > cheap_back.score(&back));
+}
+
+#[test]
+fn test_rank_and_reorient() {
+    let ergonomics = Ergonomics::default();
+    let sides = (0..4)
+        .map(|times| {
+            SUNE.moves
+                .iter()
+                .map(|op| rotate_y(op, times))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    let ranked = ergonomics.rank(sides.clone(), |alg| alg);
+    assert_eq!(ranked[0], SUNE.moves);
+
+    let back = &sides[1];
+    let rewritten = ergonomics.reorient(back);
+    let rotation = rewritten.rotation.as_ref().expect("a rotation");
+    assert!(["y", "y'", "y2"].contains(&rotation.notation.as_str()));
+    assert!(solves(back, &rewritten.moves()));
+    assert!(ergonomics.score(&rewritten.moves()) < ergonomics.score(back));
+    assert_eq!(
+        rewritten.before.len() + rewritten.after.len(),
+        SUNE.moves.len()
+    );
+    let awkward = |alg: &[&T]| {
+        alg.iter()
+            .filter(|op| matches!(op.face(), Some(CubeFace::B | CubeFace::D)))
+            .count()
+    };
+    assert!(awkward(&rewritten.after) < awkward(back));
+    // the rotation is written as such, not as turns of every layer
+    assert!(rewritten
+        .to_string()
+        .split(' ')
+        .any(|word| word == rotation.notation));
+    // nothing to gain on an algorithm already played from the right
+    let kept = ergonomics.reorient(SUNE.moves);
+    assert_eq!(kept.rotation, None);
+    assert_eq!(kept.moves(), SUNE.moves);
+    assert_eq!(kept.to_string(), "R U R' U R U2 R'");
+}
+
+#[cfg(feature = "singmaster")]
+#[test]
+fn test_rank_db() {
+    use rubik::algdb::AlgDb;
+
+    let db = AlgDb::parse(
+        "[OLL]
+case OLL 27 / Sune
+  alg B U B' U B U2 B'
+  alg R U R' U R U2 R'",
+    )
+    .unwrap();
+    let case = db.case("OLL", "OLL 27 / Sune").expect("a Sune");
+    let ranked = case.ranked(&Ergonomics::default());
+    assert_eq!(ranked[0].notation, "R U R' U R U2 R'");
+}
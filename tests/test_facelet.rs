@@ -0,0 +1,330 @@
+use rubik::{facelet::ColorInputError, prelude::*, tf};
+
+mod common;
+use common::scrambled::scrambled;
+
+#[test]
+fn test_facelet_indices() {
+    assert_eq!(Facelet::all().count(), 54);
+    for (index, facelet) in Facelet::all().enumerate() {
+        assert_eq!(facelet.index(), index);
+        assert_eq!(Facelet::from_index(index), Some(facelet));
+        let (slot, face) = facelet.position();
+        assert_eq!(Facelet::from_position(slot, face), Some(facelet));
+    }
+    assert!(Facelet::from_index(54).is_none());
+    assert!(Facelet::new(CubeFace::U, 3, 0).is_none());
+    assert!(Facelet::from_position(CubePosition::FUR, CubeFace::D).is_none());
+
+    // U is read with B on top, D with F on top, the side faces with U on top
+    let position = |face, row, col| Facelet::new(face, row, col).expect("a facelet").position();
+    assert_eq!(
+        position(CubeFace::U, 0, 0),
+        (CubePosition::BUL, CubeFace::U)
+    );
+    assert_eq!(
+        position(CubeFace::U, 2, 2),
+        (CubePosition::FUR, CubeFace::U)
+    );
+    assert_eq!(
+        position(CubeFace::D, 0, 0),
+        (CubePosition::FDL, CubeFace::D)
+    );
+    assert_eq!(
+        position(CubeFace::F, 0, 2),
+        (CubePosition::FUR, CubeFace::F)
+    );
+    assert_eq!(
+        position(CubeFace::R, 0, 0),
+        (CubePosition::FUR, CubeFace::R)
+    );
+    assert_eq!(
+        position(CubeFace::B, 0, 0),
+        (CubePosition::BUR, CubeFace::B)
+    );
+    assert_eq!(
+        position(CubeFace::L, 2, 2),
+        (CubePosition::FDL, CubeFace::L)
+    );
+    assert_eq!(position(CubeFace::F, 1, 1), (CubePosition::F, CubeFace::F));
+    assert_eq!(
+        Facelet::new(CubeFace::R, 1, 1).map(|facelet| facelet.to_string()),
+        Some("R5".to_string())
+    );
+}
+
+#[test]
+fn test_stickers() {
+    let rubik = Rubik::new();
+    for face in CubeFace::ALL {
+        assert!(rubik.face_stickers(face).all(|sticker| sticker == face));
+    }
+
+    // U brings the R stickers of the top row onto F
+    let rubik = scrambled(tf!(U));
+    assert_eq!(
+        rubik.face_stickers(CubeFace::F).collect::<Vec<_>>(),
+        [[CubeFace::R; 3], [CubeFace::F; 3], [CubeFace::F; 3]].concat()
+    );
+    assert_eq!(rubik.sticker(CubeFace::U, 0, 0), CubeFace::U);
+
+    // R brings the F stickers of the right column onto U
+    let rubik = scrambled(tf!(R));
+    assert_eq!(rubik.sticker(CubeFace::U, 0, 2), CubeFace::F);
+    assert_eq!(rubik.sticker(CubeFace::U, 0, 1), CubeFace::U);
+    assert_eq!(rubik.sticker(CubeFace::B, 2, 0), CubeFace::U);
+}
+
+/// the faces of `rubik` as letters of the classic colors, in facelet order
+fn colors(rubik: &Rubik) -> Vec<String> {
+    let scheme = RubikColor::classic_map();
+    Facelet::FACES
+        .iter()
+        .map(|face| {
+            rubik
+                .face_stickers(*face)
+                .map(|sticker| scheme[sticker].letter())
+                .collect()
+        })
+        .collect()
+}
+
+fn face_map(faces: &[String]) -> CubeFaceMap<&str> {
+    let [u, r, f, d, l, b] = [0, 1, 2, 3, 4, 5].map(|index| faces[index].as_str());
+    CubeFaceMap::new(f, r, u, b, l, d)
+}
+
+fn same_state(a: &Rubik, b: &Rubik) -> bool {
+    Facelet::all().all(|facelet| a.facelet(facelet) == b.facelet(facelet))
+        && Piece::all().all(|piece| a.locate(piece) == b.locate(piece))
+}
+
+#[test]
+fn test_color_input() {
+    let scheme = RubikColor::classic_map();
+    for scramble in [tf!(R, U, FI, L2, D, B), tf!(M, U, RI)] {
+        let rubik = scrambled(scramble);
+        let faces = colors(&rubik);
+        let read = Rubik::from_face_colors_with_scheme(&face_map(&faces), &scheme);
+        assert!(same_state(&read.expect("a state"), &rubik));
+        // any alphabet works when the centers give the colors
+        let digits = faces
+            .iter()
+            .map(|face| {
+                face.chars()
+                    .map(|letter| match letter {
+                        'W' => '1',
+                        'Y' => '2',
+                        'R' => '3',
+                        'O' => '4',
+                        'B' => '5',
+                        _ => '6',
+                    })
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>();
+        let read = Rubik::from_face_colors(&face_map(&digits)).expect("a state");
+        // the colors being those of the centers, the cube reads as held
+        let held = |color| {
+            CubeFace::ALL
+                .into_iter()
+                .find(|face| rubik.sticker(*face, 1, 1) == color)
+                .expect("a center of each color")
+        };
+        assert!(Facelet::all().all(|facelet| read.facelet(facelet) == held(rubik.facelet(facelet))));
+    }
+    let mut faces = colors(&Rubik::new());
+    faces[0] = "www www www".to_string();
+    let read = Rubik::from_face_colors_with_scheme(&face_map(&faces), &scheme);
+    assert!(read.expect("a state").is_solved());
+}
+
+#[test]
+fn test_color_input_errors() {
+    let scheme = RubikColor::classic_map();
+    let solved = colors(&Rubik::new());
+    let read = |edit: &dyn Fn(&mut Vec<Vec<char>>)| {
+        let mut faces = solved
+            .iter()
+            .map(|face| face.chars().collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        edit(&mut faces);
+        let faces = faces
+            .iter()
+            .map(|face| face.iter().collect::<String>())
+            .collect::<Vec<_>>();
+        Rubik::from_face_colors_with_scheme(&face_map(&faces), &scheme)
+    };
+    let facelet = |face, row, col| Facelet::new(face, row, col).expect("a facelet");
+
+    let error = read(&|faces| {
+        faces[1].pop();
+    });
+    assert_eq!(
+        error.err(),
+        Some(ColorInputError::FaceLength {
+            face: CubeFace::R,
+            found: 8
+        })
+    );
+    let error = read(&|faces| faces[0][0] = 'X').expect_err("an error");
+    assert_eq!(
+        error,
+        ColorInputError::UnknownColor {
+            facelet: facelet(CubeFace::U, 0, 0),
+            color: 'X'
+        }
+    );
+    assert_eq!(error.to_string(), "U1: unknown color X");
+    let error = read(&|faces| faces[0][0] = 'Y');
+    assert_eq!(
+        error.err(),
+        Some(ColorInputError::ColorCount {
+            color: 'W',
+            found: 8
+        })
+    );
+    // U and F centers swapped, which no way of holding the cube shows
+    let error = read(&|faces| {
+        faces[0][4] = 'R';
+        faces[2][4] = 'W';
+    });
+    assert_eq!(
+        error.err(),
+        Some(ColorInputError::ImpossibleCenters {
+            facelet: facelet(CubeFace::U, 1, 1)
+        })
+    );
+    // a yellow sticker on the white blue red corner, the blue one on the front bottom edge
+    let error = read(&|faces| {
+        faces[1][0] = 'Y';
+        faces[3][1] = 'B';
+    })
+    .expect_err("an error");
+    assert_eq!(
+        error,
+        ColorInputError::ImpossiblePiece {
+            facelets: vec![
+                facelet(CubeFace::U, 2, 2),
+                facelet(CubeFace::F, 0, 2),
+                facelet(CubeFace::R, 0, 0)
+            ],
+            colors: "WRY".to_string()
+        }
+    );
+    assert_eq!(error.to_string(), "U9 F3 R1: no piece has the colors WRY");
+    // two white red edges, and two orange blue ones
+    let error = read(&|faces| {
+        faces[5][1] = 'R';
+        faces[2][5] = 'O';
+    });
+    assert_eq!(
+        error.err(),
+        Some(ColorInputError::DuplicatePiece {
+            facelets: vec![facelet(CubeFace::U, 0, 1), facelet(CubeFace::B, 0, 1)],
+            colors: "WR".to_string()
+        })
+    );
+
+    // the centers give the colors
+    let mut faces = solved.clone();
+    faces[1] = "BBBBWBBBB".to_string();
+    let error = Rubik::from_face_colors(&face_map(&faces));
+    assert_eq!(
+        error.err(),
+        Some(ColorInputError::DuplicateCenter {
+            facelet: facelet(CubeFace::R, 1, 1),
+            color: 'W'
+        })
+    );
+}
+
+#[test]
+fn test_unsolvable_input() {
+    let scheme = RubikColor::classic_map();
+    let facelet = |face, row, col| Facelet::new(face, row, col).expect("a facelet");
+    let index = |face, row, col| facelet(face, row, col).index() % 9;
+    let corner = vec![
+        facelet(CubeFace::U, 2, 2),
+        facelet(CubeFace::F, 0, 2),
+        facelet(CubeFace::R, 0, 0),
+    ];
+    let read = |rubik: &Rubik, edit: &dyn Fn(&mut [Vec<char>])| {
+        let mut faces = colors(rubik)
+            .iter()
+            .map(|face| face.chars().collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        edit(&mut faces);
+        let faces = faces
+            .iter()
+            .map(|face| face.iter().collect::<String>())
+            .collect::<Vec<_>>();
+        Rubik::from_face_colors_with_scheme(&face_map(&faces), &scheme)
+    };
+    // the corner at U9 F3 R1 twisted on its own, also with the cube held turned by x
+    let twist = |faces: &mut [Vec<char>]| {
+        let (u, f, r) = (
+            index(CubeFace::U, 2, 2),
+            index(CubeFace::F, 0, 2),
+            index(CubeFace::R, 0, 0),
+        );
+        let (a, b, c) = (faces[0][u], faces[2][f], faces[1][r]);
+        (faces[0][u], faces[2][f], faces[1][r]) = (c, a, b);
+    };
+    for rubik in [Rubik::new(), scrambled(tf!(R, MI, LI))] {
+        let error = read(&rubik, &twist).expect_err("a twisted corner");
+        assert_eq!(
+            error,
+            ColorInputError::Unsolvable {
+                reason: "the corner twists do not add up",
+                pieces: vec![corner.clone()],
+            }
+        );
+    }
+    // once scrambled, every corner left twisted may be the one misread
+    let error = read(&scrambled(tf!(M, U, RI)), &twist);
+    assert!(matches!(
+        error,
+        Err(ColorInputError::Unsolvable {
+            reason: "the corner twists do not add up",
+            ..
+        })
+    ));
+    assert_eq!(
+        read(&Rubik::new(), &twist)
+            .expect_err("an error")
+            .to_string(),
+        "U9 F3 R1: unsolvable, the corner twists do not add up"
+    );
+    // the white red edge flipped
+    let error = read(&Rubik::new(), &|faces| {
+        faces[0][7] = 'R';
+        faces[2][1] = 'W';
+    });
+    assert_eq!(
+        error.err(),
+        Some(ColorInputError::Unsolvable {
+            reason: "the edge flips do not add up",
+            pieces: vec![vec![facelet(CubeFace::U, 2, 1), facelet(CubeFace::F, 0, 1)]],
+        })
+    );
+    // the white red and white blue edges swapped
+    let error = read(&Rubik::new(), &|faces| {
+        faces[2][1] = 'B';
+        faces[1][1] = 'R';
+    })
+    .expect_err("two swapped edges");
+    let ColorInputError::Unsolvable { reason, pieces } = error else {
+        panic!("an unsolvable cube");
+    };
+    assert_eq!(reason, "the corner and edge permutations differ in parity");
+    assert_eq!(pieces.len(), 2);
+    assert!(pieces.contains(&vec![
+        facelet(CubeFace::U, 2, 1),
+        facelet(CubeFace::F, 0, 1)
+    ]));
+    assert!(pieces.contains(&vec![
+        facelet(CubeFace::U, 1, 2),
+        facelet(CubeFace::R, 0, 1)
+    ]));
+}
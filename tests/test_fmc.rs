@@ -0,0 +1,142 @@
+use rubik::{
+    prelude::*,
+    solver::{
+        enumerate::EnumerateSolver,
+        fmc::{
+            dr_moves, dr_trigger, eo_moves, htr_moves, is_dr, is_eo, is_htr, DrSolver, EoSolver,
+            HtrSolver, Reductions, AXES,
+        },
+        RubikMultiSolver, RubikSolver,
+    },
+    tf,
+};
+
+mod common;
+use common::scrambled::scrambled;
+
+/// a long walk through `moves`
+fn walk(moves: &[&'static RubikLayerTransform]) -> Rubik {
+    let mut rubik = Rubik::new();
+    (0..40).for_each(|i| moves[i * 7 % moves.len()].apply_on(&mut rubik));
+    rubik
+}
+
+#[test]
+fn test_subgroups() {
+    for axis in AXES {
+        let eo = walk(&eo_moves(axis));
+        assert!(is_eo(&eo, axis), "{axis:?}");
+        let dr = walk(&dr_moves(axis));
+        assert!(is_dr(&dr, axis), "{axis:?}");
+    }
+    assert!(is_dr(&scrambled(tf!(U, R2, D)), CubeFace::U));
+    assert!(!is_dr(&scrambled(tf!(U, R2, D)), CubeFace::F));
+    assert!(!is_dr(&scrambled(tf!(R)), CubeFace::U));
+    assert!(is_dr(&scrambled(tf!(R)), CubeFace::L));
+    assert!(is_htr(&walk(&htr_moves())));
+    assert!(is_htr(&scrambled(tf!(R2, U2))));
+    assert!(!is_htr(&scrambled(tf!(R2, U))));
+}
+
+#[test]
+fn test_eo() {
+    let rubik = scrambled(tf!(F, R, DI, B));
+    for axis in AXES {
+        let solutions = EoSolver { axis, max_len: 6 }
+            .solutions(rubik.clone())
+            .take(20)
+            .map(|state| state.collect())
+            .collect::<Vec<_>>();
+        assert!(!solutions.is_empty(), "{axis:?}");
+        assert!(solutions.windows(2).all(|w| w[0].1.len() <= w[1].1.len()));
+        assert!(solutions.iter().all(|(solved, _)| is_eo(solved, axis)));
+    }
+    // optimal on the F/B axis
+    let (_, ops) = EoSolver {
+        axis: CubeFace::F,
+        max_len: 6,
+    }
+    .solve(rubik.clone())
+    .collect();
+    let brute = EnumerateSolver::htm(ops.len())
+        .with_checker(|rubik| is_eo(rubik, CubeFace::F))
+        .solve(rubik)
+        .depth();
+    assert_eq!(ops.len(), brute);
+    // already oriented
+    let state = EoSolver {
+        axis: CubeFace::U,
+        max_len: 4,
+    }
+    .solve(scrambled(tf!(F)));
+    assert_eq!(state.depth(), 0);
+}
+
+#[test]
+fn test_dr() {
+    let rubik = scrambled(tf!(R, U, L2, D, R, F2, U));
+    assert!(is_eo(&rubik, CubeFace::F));
+    let moves = eo_moves(CubeFace::F);
+    let solutions = DrSolver {
+        axis: CubeFace::U,
+        eo_axis: CubeFace::F,
+        max_len: 8,
+    }
+    .solutions(rubik)
+    .take(5)
+    .map(|state| state.collect())
+    .collect::<Vec<_>>();
+    assert!(!solutions.is_empty());
+    assert!(solutions.windows(2).all(|w| w[0].1.len() <= w[1].1.len()));
+    for (solved, ops) in &solutions {
+        assert!(is_dr(solved, CubeFace::U));
+        assert!(ops.iter().all(|op| moves.contains(op)));
+        // the last move of a shortest domino reduction is always a trigger
+        let trigger = dr_trigger(ops, CubeFace::U).expect("ends with a trigger");
+        assert!(ops.ends_with(&trigger));
+    }
+    assert_eq!(
+        dr_trigger(
+            &[
+                &RubikLayerTransform::R,
+                &RubikLayerTransform::U2,
+                &RubikLayerTransform::RI
+            ],
+            CubeFace::U
+        ),
+        Some(vec![
+            &RubikLayerTransform::R,
+            &RubikLayerTransform::U2,
+            &RubikLayerTransform::RI
+        ])
+    );
+    assert_eq!(dr_trigger(&[&RubikLayerTransform::R2], CubeFace::U), None);
+}
+
+#[test]
+fn test_htr() {
+    let rubik = scrambled(tf!(U, R2, D, F2, UI, L2, U));
+    assert!(is_dr(&rubik, CubeFace::U));
+    let moves = dr_moves(CubeFace::U);
+    let (solved, ops) = HtrSolver {
+        axis: CubeFace::U,
+        max_len: 8,
+    }
+    .solve(rubik)
+    .collect();
+    assert!(is_htr(&solved));
+    assert!(ops.iter().all(|op| moves.contains(op)));
+}
+
+#[test]
+fn test_reductions() {
+    let reductions = Reductions::new(scrambled(tf!(F, U, R)), 4);
+    let (axis, mut eos) = reductions.eo().next().expect("three axes");
+    assert_eq!(axis, CubeFace::F);
+    let eo = eos.next().expect("an edge orientation");
+    let drs = reductions
+        .dr(&eo, axis)
+        .map(|(axis, _)| axis)
+        .collect::<Vec<_>>();
+    assert_eq!(drs, [CubeFace::R, CubeFace::U]);
+}
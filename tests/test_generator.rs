@@ -0,0 +1,46 @@
+use rubik::{
+    prelude::*,
+    solver::{
+        beginner::SUNE,
+        generator::AlgGenerator,
+        last_layer::{is_aligned, is_oriented, OLL},
+        one_look::LlMoves,
+        steps::notation,
+    },
+};
+
+mod common;
+use common::case_of::case_of;
+
+#[test]
+fn test_generate() {
+    let case = case_of(&SUNE, &RubikLayerTransform::UNIT);
+    let exact = AlgGenerator::new(LlMoves::Ru.moves(), 9).generate(&case);
+    assert_eq!(exact.len(), 1);
+    assert_eq!(exact[0].to_string(), notation(SUNE.moves.iter().copied()));
+
+    // up to a last turn of U, Sune followed by any turn of U reaching the goal too late
+    let algs = AlgGenerator::new(LlMoves::Ru.moves(), 9)
+        .with_goal(is_aligned as fn(&Rubik) -> bool)
+        .generate(&case);
+    let algs = algs.iter().map(|alg| alg.to_string()).collect::<Vec<_>>();
+    assert_eq!(algs, [notation(SUNE.moves.iter().copied())]);
+}
+
+#[test]
+fn test_goal_and_score() {
+    let oll_45 = OLL.iter().find(|alg| alg.name == "OLL 45").expect("OLL 45");
+    let case = case_of(oll_45, &RubikLayerTransform::UNIT);
+    let generator = AlgGenerator::new(LlMoves::Ruf.moves(), 6)
+        .with_goal(is_oriented as fn(&Rubik) -> bool)
+        .with_score(|moves| {
+            moves
+                .iter()
+                .filter(|op| op.face() == Some(CubeFace::F))
+                .count() as f64
+        });
+    let algs = generator.generate(&case);
+    assert!(algs.iter().any(|alg| alg.moves == oll_45.moves));
+    assert!(algs.iter().all(|alg| alg.moves.len() == 6));
+    assert!(algs.windows(2).all(|w| w[0].score <= w[1].score));
+}
@@ -0,0 +1,103 @@
+use rubik::{
+    prelude::*,
+    solver::{
+        insertion::{cycles, InsertionFinder},
+        last_layer::PLL,
+    },
+    tf,
+};
+
+mod common;
+use common::scrambled::scrambled;
+
+fn solves(scramble: &Rubik, moves: &[&'static RubikLayerTransform]) -> bool {
+    let mut rubik = scramble.clone();
+    moves.iter().for_each(|op| op.apply_on(&mut rubik));
+    rubik.is_solved()
+}
+
+#[test]
+fn test_cycles() {
+    let commutator = tf!(R, U, RI, D, R, UI, RI, DI);
+    let found = cycles(&scrambled(commutator));
+    assert_eq!(found.len(), 1);
+    assert!(found[0].is_corner());
+    assert_eq!(found[0].slots.len(), 3);
+    assert_eq!(found[0].twist, 0);
+    assert!(found[0].to_string().starts_with("corner 3-cycle"));
+
+    // a quarter turn cycles four corners and four edges
+    let found = cycles(&scrambled(tf!(U)));
+    assert_eq!(found.len(), 2);
+    assert_eq!(found[0].slots.len(), 4);
+    assert!(!found[1].is_corner());
+    assert!(cycles(&scrambled(tf!(U, U, U, U))).is_empty());
+}
+
+#[test]
+fn test_corner_insertion() {
+    // a solution with a commutator in the middle, the skeleton leaving it out
+    let before = tf!(F, R2, D, BI);
+    let after = tf!(L, U2, FI);
+    let solution = tf!(
+        before.clone(),
+        tf!(R, U, RI, D, R, UI, RI, DI),
+        after.clone()
+    );
+    let scramble = scrambled(solution.inverse());
+    let skeleton = tf!(before, after);
+
+    let insertions = InsertionFinder::corner_commutators().insertions(&scramble, &skeleton);
+    assert!(!insertions.is_empty());
+    assert!(insertions
+        .windows(2)
+        .all(|w| w[0].moves.len() <= w[1].moves.len()));
+    for insertion in &insertions {
+        assert_eq!(insertion.solves.len(), 1);
+        assert!(solves(&scramble, &insertion.moves), "{insertion}");
+    }
+    assert!(insertions[0].moves.len() <= 15);
+    assert!(insertions
+        .iter()
+        .any(|insertion| insertion.position == 4 && insertion.moves.len() == 15));
+}
+
+#[test]
+fn test_two_cycles() {
+    let solution = tf!(
+        tf!(R, U, RI, D, R, UI, RI, DI),
+        B2,
+        tf!(L, U, LI, D, L, UI, LI, DI)
+    );
+    let scramble = scrambled(solution.inverse());
+    let skeleton = tf!(B2);
+    let finder = InsertionFinder::corner_commutators();
+
+    let mut residual = scramble.clone();
+    residual.execute(&skeleton);
+    assert_eq!(cycles(&residual).len(), 2);
+    let first = finder.insertions(&scramble, &skeleton);
+    assert!(first.iter().all(|insertion| insertion.solves.len() == 1));
+    let skeleton = RubikTransform::from(first[0].moves.iter().map(|op| **op).collect::<Vec<_>>());
+    let second = finder.insertions(&scramble, &skeleton);
+    assert!(solves(&scramble, &second[0].moves));
+}
+
+#[test]
+fn test_custom_algs() {
+    let t_perm = PLL.iter().find(|alg| alg.name == "T perm").expect("T perm");
+    let scramble = scrambled(tf!(
+        F2,
+        RubikTransform::from(t_perm.moves.iter().map(|op| **op).collect::<Vec<_>>()),
+        F2
+    ));
+    let finder = InsertionFinder::new([t_perm.moves.to_vec()]);
+    let insertions = finder.insertions(&scramble, &tf!(F2, F2));
+    // the T perm and its inverse, both cancelling one move
+    assert_eq!(insertions.len(), 2);
+    for insertion in &insertions {
+        assert_eq!(insertion.position, 1);
+        assert_eq!(insertion.moves.len(), 15);
+        assert!(solves(&scramble, &insertion.moves));
+    }
+}
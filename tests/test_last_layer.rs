@@ -0,0 +1,212 @@
+use rubik::{
+    prelude::*,
+    solver::{
+        beginner::{ANTISUNE, A_PERM, SUNE, UA_PERM, UB_PERM},
+        last_layer::{
+            is_aligned, is_oriented, recognize_ll, recognize_oll, recognize_pll, LlSet, LlState,
+            OLL, PLL,
+        },
+        mask::GoalMask,
+        steps::notation,
+    },
+};
+
+mod common;
+use common::case_of::case_of;
+
+#[test]
+fn test_algs_keep_f2l() {
+    let f2l = GoalMask::f2l(CubeFace::D);
+    for alg in OLL.iter().chain(PLL.iter()) {
+        let mut rubik = Rubik::new();
+        for op in alg.moves {
+            op.apply_on(&mut rubik);
+        }
+        assert!(
+            f2l.is_reached(&rubik),
+            "{} breaks the first two layers",
+            alg.name
+        );
+    }
+    for alg in &PLL {
+        assert!(is_oriented(&case_of(alg, &RubikLayerTransform::UNIT)));
+    }
+}
+
+#[test]
+fn test_algs_cancelled() {
+    for alg in OLL.iter().chain(PLL.iter()) {
+        let cancelled = RubikLayerTransform::cancel(alg.moves.iter().copied());
+        assert!(
+            cancelled == alg.moves,
+            "{} cancels to {}",
+            alg.name,
+            notation(cancelled)
+        );
+    }
+}
+
+#[test]
+fn test_recognize_oll() {
+    for alg in &OLL {
+        for pre_auf in [&RubikLayerTransform::UNIT, &RubikLayerTransform::U] {
+            let rubik = case_of(alg, pre_auf);
+            let case = recognize_oll(&rubik).expect("an OLL case");
+            assert_eq!(case.alg.name, alg.name);
+            let mut rubik = rubik;
+            for op in case.ops() {
+                op.apply_on(&mut rubik);
+            }
+            assert!(is_oriented(&rubik));
+        }
+    }
+    assert!(recognize_oll(&Rubik::new()).is_none());
+}
+
+#[test]
+fn test_recognize_pll() {
+    for alg in &PLL {
+        for pre_auf in [&RubikLayerTransform::UNIT, &RubikLayerTransform::UI] {
+            let rubik = case_of(alg, pre_auf);
+            let case = recognize_pll(&rubik).expect("a PLL case");
+            assert_eq!(case.alg.name, alg.name);
+            let mut rubik = rubik;
+            for op in case.ops() {
+                op.apply_on(&mut rubik);
+            }
+            assert!(is_aligned(&rubik));
+        }
+    }
+    for (alg, name) in [
+        (A_PERM, "Aa perm"),
+        (UA_PERM, "Ua perm"),
+        (UB_PERM, "Ub perm"),
+    ] {
+        let case = recognize_pll(&case_of(&alg, &RubikLayerTransform::UNIT));
+        assert_eq!(case.expect("a PLL case").alg.name, name);
+    }
+    let mut rubik = Rubik::new();
+    RubikLayerTransform::U.apply_on(&mut rubik);
+    assert!(recognize_pll(&rubik).is_none());
+}
+
+#[test]
+fn test_ll_state() {
+    let rubik = case_of(&PLL[15], &RubikLayerTransform::UNIT);
+    let state = LlState::new(&rubik).expect("first two layers solved");
+    let mut turned = rubik.clone();
+    RubikLayerTransform::U.apply_on(&mut turned);
+    let turned = LlState::new(&turned).expect("first two layers solved");
+    assert_eq!(turned, state.turn());
+    let mut before = Rubik::new();
+    RubikLayerTransform::U.apply_on(&mut before);
+    for op in PLL[15].moves.iter().rev() {
+        op.inverse().apply_on(&mut before);
+    }
+    let before = LlState::new(&before).expect("first two layers solved");
+    assert_ne!(before, state);
+    assert_eq!(before.canonical(), state.canonical());
+    assert!(LlState::new(&case_of(&SUNE, &RubikLayerTransform::UNIT))
+        .expect("first two layers solved")
+        .edges_oriented());
+    let mut broken = Rubik::new();
+    RubikLayerTransform::R.apply_on(&mut broken);
+    assert!(LlState::new(&broken).is_none());
+}
+
+#[test]
+fn test_recognize_ll() {
+    let sune = recognize_ll(&case_of(&SUNE, &RubikLayerTransform::U)).expect("a last layer");
+    let oll = sune.oll.expect("an OLL case");
+    assert_eq!(oll.label(), "OLL 27 / Sune");
+    assert_eq!(oll.pre_auf, &RubikLayerTransform::U);
+    assert!(sune
+        .coll
+        .as_deref()
+        .is_some_and(|coll| coll.starts_with("COLL S ")));
+    assert!(sune
+        .zbll
+        .as_deref()
+        .is_some_and(|zbll| zbll.starts_with("ZBLL S ")));
+
+    let t_perm = PLL.iter().find(|alg| alg.name == "T perm").expect("T perm");
+    let case = recognize_ll(&case_of(t_perm, &RubikLayerTransform::U2)).expect("a last layer");
+    assert!(case.oll.is_none());
+    assert_eq!(case.pll.expect("a PLL case").label(), "PLL T");
+    assert_eq!(case.to_string().split(", ").next(), Some("PLL T"));
+    assert!(case
+        .zbll
+        .as_deref()
+        .is_some_and(|zbll| zbll.starts_with("ZBLL PLL ")));
+
+    // the same case seen from another angle gets the same names
+    let other = recognize_ll(&case_of(t_perm, &RubikLayerTransform::UI)).expect("a last layer");
+    assert_eq!(other.coll, case.coll);
+    assert_eq!(other.zbll, case.zbll);
+
+    let solved = recognize_ll(&Rubik::new()).expect("a last layer");
+    assert_eq!(solved.to_string(), "solved");
+    assert!(recognize_ll(&case_of(&OLL[0], &RubikLayerTransform::UNIT))
+        .expect("a last layer")
+        .zbll
+        .is_none());
+}
+
+#[test]
+fn test_case_sets() {
+    assert_eq!(LlState::all().count(), 62208);
+    let cases = LlSet::ALL.map(|set| set.cases(false));
+    assert_eq!(cases.each_ref().map(Vec::len), [57, 21, 42, 493, 3915]);
+    for (set, cases) in LlSet::ALL.iter().zip(&cases) {
+        let total = cases.iter().map(|case| case.probability).sum::<f64>();
+        let skip = set.skip(false).probability;
+        assert!((total + skip - 1.0).abs() < 1e-9, "{}", set.name());
+    }
+    assert_eq!(LlSet::Oll.skip(false).probability, 1.0 / 216.0);
+    assert_eq!(LlSet::Pll.skip(false).probability, 1.0 / 72.0);
+    // an A perm comes up once in 18 oriented last layers, an H perm once in 72
+    for (alg, states) in [(&PLL[0], 16), (&PLL[8], 4)] {
+        let state = LlState::new(&case_of(alg, &RubikLayerTransform::UNIT));
+        let key = LlSet::Pll.key(state.expect("first two layers solved"), false);
+        let case = cases[1].iter().find(|case| case.key == key);
+        assert_eq!(case.map(|case| case.states), Some(states), "{}", alg.name);
+    }
+
+    // Sune and Antisune mirror each other
+    let state = |alg| LlState::new(&case_of(alg, &RubikLayerTransform::UNIT)).expect("a state");
+    let (sune, antisune) = (state(&SUNE), state(&ANTISUNE));
+    assert_eq!(sune.mirror().mirror(), sune);
+    assert_eq!(sune.mirror().oll_key(), antisune.oll_key());
+    assert_eq!(LlSet::Oll.key(sune, true), LlSet::Oll.key(antisune, true));
+    assert!(LlSet::Oll.cases(true).len() < cases[0].len());
+    assert!(LlSet::Pll.cases(true).len() < cases[1].len());
+
+    // every example can be set up
+    for case in &cases[2] {
+        let mut rubik = Rubik::new();
+        let setup = case.example.setup().expect("a reachable state");
+        setup.iter().for_each(|op| op.apply_on(&mut rubik));
+        assert_eq!(LlState::new(&rubik), Some(case.example));
+    }
+}
+
+#[test]
+fn test_named_cases() {
+    let coll = LlSet::Coll.named_cases();
+    assert_eq!(coll.len(), 42);
+    assert_eq!(coll[0].0, "COLL AS 1");
+    assert_eq!(LlSet::Oll.named_cases()[26].0, "OLL 27 / Sune");
+    assert_eq!(LlSet::Pll.named_cases().len(), 21);
+    assert!(LlSet::OneLook.named_cases().is_empty());
+    // the example of each case gets its name back
+    for set in [LlSet::Coll, LlSet::Zbll] {
+        for (name, case) in set.named_cases() {
+            let mut rubik = Rubik::new();
+            let setup = case.example.setup().expect("a reachable state");
+            setup.iter().for_each(|op| op.apply_on(&mut rubik));
+            let ll = recognize_ll(&rubik).expect("a last layer");
+            let recognized = if set == LlSet::Coll { ll.coll } else { ll.zbll };
+            assert_eq!(recognized, Some(name));
+        }
+    }
+}
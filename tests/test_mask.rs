@@ -0,0 +1,74 @@
+use rubik::{
+    prelude::*,
+    solver::{
+        enumerate::EnumerateSolver,
+        mask::{GoalMask, MaskSolver, PieceGoal},
+        RubikMultiSolver, RubikSolver,
+    },
+    tf,
+};
+
+mod common;
+use common::scrambled::scrambled;
+
+#[test]
+fn test_presets() {
+    let cross = GoalMask::cross(CubeFace::D);
+    assert_eq!(cross.pieces().count(), 5);
+    assert_eq!(cross.get(CubePosition::FD), PieceGoal::Solved);
+    assert_eq!(cross.get(CubePosition::FDR), PieceGoal::Ignored);
+    let xcross = GoalMask::xcross(CubeFace::D, CubePosition::FDR);
+    assert_eq!(xcross.get(CubePosition::FR), PieceGoal::Solved);
+    assert_eq!(xcross.pieces().count(), 7);
+    assert_eq!(GoalMask::f2l(CubeFace::D).pieces().count(), 17);
+    assert_eq!(GoalMask::block(CubePosition::BDL).pieces().count(), 7);
+
+    let rubik = scrambled(tf!(R, U, RI));
+    assert!(cross.is_reached(&rubik));
+    assert!(!xcross.is_reached(&rubik));
+    assert!(!GoalMask::solved().is_reached(&rubik));
+    assert!(GoalMask::solved().is_reached(&Rubik::new()));
+}
+
+#[test]
+fn test_optimal_cross() {
+    let rubik = scrambled(tf!(F, R, DI, L2, U, B));
+    let cross = GoalMask::cross(CubeFace::D);
+    let (solved, ops) = MaskSolver::htm(&cross).solve(rubik.clone()).collect();
+    assert!(cross.is_reached(&solved));
+    // nothing shorter exists
+    let brute_force = EnumerateSolver::htm(ops.len())
+        .with_goal(cross)
+        .solutions(rubik)
+        .next()
+        .unwrap();
+    assert_eq!(brute_force.depth(), ops.len());
+}
+
+#[test]
+fn test_xcross_and_block() {
+    let rubik = scrambled(tf!(R, U, F, DI, L));
+    let xcross = GoalMask::xcross(CubeFace::D, CubePosition::FDR);
+    let (solved, _) = MaskSolver::htm(&xcross).solve(rubik.clone()).collect();
+    assert!(xcross.is_reached(&solved));
+
+    let block = GoalMask::block(CubePosition::BDL);
+    let (solved, ops) = MaskSolver::htm(&block).solve(rubik).collect();
+    assert!(block.is_reached(&solved));
+    assert!(ops.len() <= 5);
+}
+
+#[test]
+fn test_oriented() {
+    let edges = CubePosition::all().filter(|p| p.is_edge());
+    let eo = GoalMask::new().orient(edges);
+    let rubik = scrambled(tf!(R, F));
+    assert!(!eo.is_reached(&rubik));
+    let solutions = MaskSolver::htm(&eo)
+        .solutions(rubik)
+        .take(4)
+        .map(|s| s.depth())
+        .collect::<Vec<_>>();
+    assert_eq!(solutions[0], 1);
+    assert!(solutions.windows(2).all(|w| w[0] <= w[1]));
+}
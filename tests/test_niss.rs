@@ -0,0 +1,53 @@
+use rubik::{
+    prelude::*,
+    solver::niss::{NissSession, Side},
+    tf,
+};
+
+mod common;
+use common::scrambled::scrambled;
+
+#[test]
+fn test_niss_session() {
+    let mut session = NissSession::new(scrambled(tf!(R, U, FI, D2)));
+    session.play(Side::Normal, &tf!(D2, F));
+    assert_eq!(
+        session.state(Side::Normal),
+        scrambled(tf!(R, U, FI, D2, D2, F))
+    );
+    session.play(Side::Inverse, &tf!(R, U));
+    assert_eq!(
+        session.state(Side::Inverse),
+        session.state(Side::Normal).inverse()
+    );
+    assert!(session.is_solved());
+    assert_eq!(session.to_string(), "D2 F (R U)");
+    // D2 F U' R'
+    assert_eq!(
+        session.solution(),
+        [
+            &RubikLayerTransform::D2,
+            &RubikLayerTransform::F,
+            &RubikLayerTransform::UI,
+            &RubikLayerTransform::RI
+        ]
+    );
+
+    // moves cancel across the sides
+    assert_eq!(session.undo(Side::Inverse), Some(&RubikLayerTransform::U));
+    session.play(Side::Inverse, &tf!(FI, U));
+    assert_eq!(session.solution().len(), 5);
+    assert!(!session.is_solved());
+}
+
+#[cfg(feature = "singmaster")]
+#[test]
+fn test_parse_niss() {
+    use rubik::parser::singmaster::{parse, parse_niss};
+    let (normal, inverse) = parse_niss("F (R U) (D B)2 L' (U)").unwrap();
+    assert_eq!(normal.ops(), parse("F D B D B L'").unwrap().ops());
+    assert_eq!(inverse.ops(), parse("R U U").unwrap().ops());
+    // plain parsing still groups
+    assert_eq!(parse("(R U)").unwrap().ops(), parse("R U").unwrap().ops());
+    assert!(parse_niss("F (R").is_err());
+}
@@ -0,0 +1,116 @@
+use std::path::PathBuf;
+
+use rubik::{
+    prelude::*,
+    solver::{
+        beginner::SUNE,
+        last_layer::{LlSet, LlState, OLL, PLL},
+        one_look::{LlMoves, LlSolveError, OneLookSolver},
+    },
+    tables::TableCache,
+};
+
+mod common;
+use common::case_of::case_of;
+
+fn cache_dir(name: &str) -> PathBuf {
+    let dir =
+        std::env::temp_dir().join(format!("rubik-test-one-look-{name}-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    dir
+}
+
+fn solves(rubik: &Rubik, moves: &[&'static RubikLayerTransform]) -> bool {
+    let mut rubik = rubik.clone();
+    moves.iter().for_each(|op| op.apply_on(&mut rubik));
+    rubik.is_solved()
+}
+
+#[test]
+fn test_one_look() {
+    let solver = OneLookSolver::new(LlMoves::Ru, 8);
+    let sune = case_of(&SUNE, &RubikLayerTransform::U);
+    // solving only looks cases up, searching them is asked for
+    assert_eq!(solver.solve(&sune), Err(LlSolveError::NotSearched));
+    let solution = solver.search(&sune).expect("a Sune");
+    assert_eq!(solution.alg.len(), 7);
+    assert_eq!(solution.pre_auf, &RubikLayerTransform::U);
+    assert!(solves(&sune, &solution.moves()), "{solution}");
+    assert_eq!(solver.solve(&sune), Ok(solution));
+
+    let solved = solver.search(&Rubik::new()).expect("solved");
+    assert!(solved.moves().is_empty());
+    let mut turned = Rubik::new();
+    RubikLayerTransform::U.apply_on(&mut turned);
+    assert_eq!(solver.solve(&turned).expect("solved").moves().len(), 1);
+
+    // F R U R' U' F' is out of <R, U>, and the first two layers must be solved
+    let oll_45 = OLL.iter().find(|alg| alg.name == "OLL 45").expect("OLL 45");
+    let oll_45 = case_of(oll_45, &RubikLayerTransform::U2);
+    assert_eq!(solver.search(&oll_45), Err(LlSolveError::NotFound));
+    let solution = OneLookSolver::new(LlMoves::Ruf, 6)
+        .search(&oll_45)
+        .expect("an OLL 45");
+    assert_eq!(solution.alg.len(), 6);
+    assert!(solves(&oll_45, &solution.moves()));
+    let mut broken = Rubik::new();
+    RubikLayerTransform::R.apply_on(&mut broken);
+    assert_eq!(solver.search(&broken), Err(LlSolveError::F2lUnsolved));
+}
+
+#[test]
+fn test_precompute() {
+    let dir = cache_dir("precompute");
+    let solver = OneLookSolver::with_cache(LlMoves::Ru, 11, TableCache::new(&dir));
+    // the H and U perms and the solved last layer
+    assert_eq!(solver.precompute(LlSet::Pll), 4);
+    // the pattern tables and the algorithms go to the cache of the solver
+    assert!(dir.join("one_look_ru_corners.tbl").exists());
+    assert!(dir.join("one_look_ru.tbl").exists());
+    let ua_perm = PLL
+        .iter()
+        .find(|alg| alg.name == "Ua perm")
+        .expect("Ua perm");
+    let ua_perm = case_of(ua_perm, &RubikLayerTransform::U2);
+    let solution = solver.solve(&ua_perm).expect("a Ua perm");
+    assert_eq!(solution.alg.len(), 11);
+    assert!(solves(&ua_perm, &solution.moves()));
+    // a solver on the same cache starts from the algorithms saved there
+    let loaded = OneLookSolver::with_cache(LlMoves::Ru, 11, TableCache::new(&dir));
+    assert_eq!(loaded.solve(&ua_perm), Ok(solution));
+}
+
+#[cfg(feature = "one-look-table")]
+#[test]
+fn test_htm() {
+    // every case comes with the library, the solved one included
+    let solver = OneLookSolver::new(LlMoves::Htm, 16);
+    assert_eq!(solver.precompute(LlSet::OneLook), 3916);
+    let mut longest = 0;
+    // every state of the last layer, not only the canonical ones
+    for state in LlState::all() {
+        let mut case = Rubik::new();
+        let setup = state.setup().expect("a reachable state");
+        setup.iter().for_each(|op| op.apply_on(&mut case));
+        let solution = solver.solve(&case).expect("a last layer");
+        assert!(solves(&case, &solution.moves()), "{solution}");
+        longest = longest.max(solution.alg.len());
+    }
+    assert_eq!(longest, 16);
+
+    // the E and V perms are the longest PLLs
+    let pll = |name| {
+        case_of(
+            PLL.iter().find(|alg| alg.name == name).expect("a PLL"),
+            &RubikLayerTransform::UNIT,
+        )
+    };
+    for (name, len) in [("E perm", 14), ("V perm", 14), ("H perm", 9)] {
+        let solution = solver.solve(&pll(name)).expect("a PLL");
+        assert_eq!(solution.alg.len(), len, "{name}");
+    }
+    assert_eq!(
+        OneLookSolver::new(LlMoves::Htm, 13).solve(&pll("E perm")),
+        Err(LlSolveError::NotFound)
+    );
+}
@@ -0,0 +1,125 @@
+use rubik::{
+    partial::{Completion, PartialState},
+    prelude::*,
+    tf,
+};
+
+mod common;
+use common::scrambled::scrambled;
+
+fn shows(rubik: &Rubik, state: &PartialState) -> bool {
+    Facelet::all().all(|facelet| {
+        state.facelets[facelet.index()].is_none_or(|home| home == rubik.facelet(facelet))
+    })
+}
+
+#[test]
+fn test_count() {
+    let unknown = PartialState::new([None; 54]);
+    assert_eq!(unknown.count(), 43_252_003_274_489_856_000);
+
+    let rubik = scrambled(tf!(R, U, FI, L2, D, B, RI));
+    let known = PartialState::masked(&rubik, |_| true);
+    assert_eq!(known.count(), 1);
+
+    // the stickers of the UFR and UBL corners are unknown, the other pieces being
+    // solved: swapping them would be odd, so only their twists remain
+    let solved = Rubik::new();
+    let corners = [CubePosition::FUR, CubePosition::BUL];
+    let state = PartialState::masked(&solved, |facelet| !corners.contains(&facelet.position().0));
+    assert_eq!(state.complete(), Completion::Many(3));
+    let completions = state.completions(10);
+    assert_eq!(completions.len(), 3);
+    assert!(completions.iter().all(|rubik| shows(rubik, &state)));
+    assert!(completions.iter().any(Rubik::is_solved));
+
+    // only U, F and R show
+    let state = PartialState::masked(&rubik, |facelet| {
+        [CubeFace::U, CubeFace::F, CubeFace::R].contains(&facelet.face)
+    });
+    assert!(state.count() > 1);
+    let completions = state.completions(20);
+    assert_eq!(completions.len(), 20);
+    assert!(completions.iter().all(|rubik| shows(rubik, &state)));
+}
+
+#[test]
+fn test_complete() {
+    // an edge hidden on an otherwise known cube is the edge left
+    let rubik = scrambled(tf!(R, U, FI, L2, D, B, RI));
+    let state = PartialState::masked(&rubik, |facelet| facelet.position().0 != CubePosition::BR);
+    let Completion::Unique(completed) = state.complete() else {
+        panic!("a single completion");
+    };
+    assert!(Facelet::all().all(|facelet| completed.facelet(facelet) == rubik.facelet(facelet)));
+
+    // a flipped edge on a solved cube
+    let mut state = PartialState::masked(&Rubik::new(), |_| true);
+    let uf = Facelet::from_position(CubePosition::FU, CubeFace::U).expect("a facelet");
+    let fu = Facelet::from_position(CubePosition::FU, CubeFace::F).expect("a facelet");
+    state.facelets[uf.index()] = Some(CubeFace::F);
+    state.facelets[fu.index()] = Some(CubeFace::U);
+    assert_eq!(state.complete(), Completion::Impossible);
+    assert!(state.completions(1).is_empty());
+}
+
+#[test]
+fn test_color_input() {
+    let scheme = RubikColor::classic_map();
+    let faces = CubeFaceMap::new(
+        "RRRRRRRRR",
+        "BBBBBBBBB",
+        "WWWWWWWW?",
+        "OOOOOOOOO",
+        "GGGGGGGGG",
+        "YYYYYYYYY",
+    );
+    let state = PartialState::from_face_colors_with_scheme(&faces, &scheme).expect("a state");
+    let Completion::Unique(rubik) = state.complete() else {
+        panic!("a single completion");
+    };
+    assert!(rubik.is_solved());
+}
+
+#[test]
+fn test_held_turned() {
+    // scrambled, then held turned by x
+    let rubik = scrambled(tf!(R, U, FI, L2, D, B, RI, R, MI, LI));
+    // read from its colors as the full state reads, a sticker hidden
+    let scheme = RubikColor::classic_map();
+    let face = |face| {
+        rubik
+            .face_stickers(face)
+            .map(|sticker| scheme[sticker].letter())
+            .collect::<String>()
+    };
+    let [f, r, u, b, l, d] = [
+        CubeFace::F,
+        CubeFace::R,
+        CubeFace::U,
+        CubeFace::B,
+        CubeFace::L,
+        CubeFace::D,
+    ]
+    .map(face);
+    let full = CubeFaceMap::new(f.as_str(), &r, &u, &b, &l, &d);
+    let read = Rubik::from_face_colors_with_scheme(&full, &scheme).expect("a state");
+    let hidden = format!("{}?", &f[..8]);
+    let faces = CubeFaceMap::new(hidden.as_str(), &r, &u, &b, &l, &d);
+    let state = PartialState::from_face_colors_with_scheme(&faces, &scheme).expect("a state");
+    assert_eq!(state.complete(), Completion::Unique(read));
+    let state = PartialState::masked(&rubik, |facelet| facelet.position().0 != CubePosition::BR);
+    let Completion::Unique(completed) = state.complete() else {
+        panic!("a single completion");
+    };
+    assert!(Facelet::all().all(|facelet| completed.facelet(facelet) == rubik.facelet(facelet)));
+
+    // only the stickers of U, F and R, the U center showing the F color
+    let state = PartialState::masked(&rubik, |facelet| {
+        [CubeFace::U, CubeFace::F, CubeFace::R].contains(&facelet.face)
+    });
+    assert!(state
+        .completions(20)
+        .iter()
+        .all(|completed| shows(completed, &state)));
+}
@@ -0,0 +1,59 @@
+use rubik::{
+    piece::{Piece, PieceKind},
+    prelude::*,
+    tf,
+};
+
+mod common;
+use common::scrambled::scrambled;
+
+#[test]
+fn test_pieces() {
+    assert_eq!(Piece::all().count(), 26);
+    assert_eq!(Piece::corners().count(), 8);
+    assert_eq!(Piece::edges().count(), 12);
+    assert_eq!(Piece::centers().count(), 6);
+    assert!(Piece::new(CubePosition::C).is_none());
+
+    let ufr = Piece::from_faces([CubeFace::U, CubeFace::F, CubeFace::R]).expect("a corner");
+    assert_eq!(ufr.home(), CubePosition::FUR);
+    assert_eq!(ufr.kind(), PieceKind::Corner);
+    assert_eq!(ufr.to_string(), "UFR");
+    let dl = Piece::new(CubePosition::DLL).expect("an edge");
+    assert_eq!(dl.to_string(), "DL");
+    assert!(Piece::from_faces([CubeFace::U, CubeFace::D]).is_none());
+}
+
+#[test]
+fn test_locate() {
+    let rubik = Rubik::new();
+    for piece in Piece::all() {
+        assert_eq!(rubik.locate(piece), (piece.home(), 0));
+        assert_eq!(rubik.piece_at(piece.home()), Some(piece));
+    }
+    assert!(rubik.piece_at(CubePosition::C).is_none());
+
+    // F turns UF onto FR, flipped relative to F2 and B2
+    let rubik = scrambled(tf!(F));
+    let uf = Piece::new(CubePosition::FU).expect("an edge");
+    assert_eq!(rubik.locate(uf), (CubePosition::FR, 1));
+    assert_eq!(rubik.piece_at(CubePosition::FR), Some(uf));
+
+    // R brings UFR up to UBR, U2 across to UFL, the corner twists summing to nothing
+    let rubik = scrambled(tf!(R, U2));
+    let ufr = Piece::new(CubePosition::FUR).expect("a corner");
+    assert_eq!(rubik.locate(ufr).0, CubePosition::FUL);
+    let twist = Piece::corners()
+        .map(|piece| rubik.locate(piece).1)
+        .sum::<u8>();
+    assert_eq!(twist % 3, 0);
+    for slot in CubePosition::all().filter(|slot| *slot != CubePosition::C) {
+        let piece = rubik.piece_at(slot).expect("a piece");
+        assert_eq!(rubik.locate(piece).0, slot);
+    }
+
+    // slice turns move centers
+    let rubik = scrambled(tf!(M));
+    let u = Piece::new(CubePosition::U).expect("a center");
+    assert_eq!(rubik.locate(u), (CubePosition::F, 0));
+}
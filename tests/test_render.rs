@@ -0,0 +1,68 @@
+use rubik::{
+    prelude::*,
+    render::{net, Style},
+    tf,
+};
+
+mod common;
+use common::scrambled::scrambled;
+
+const SOLVED: &str = "      W W W
+      W W W
+      W W W
+G G G R R R B B B O O O
+G G G R R R B B B O O O
+G G G R R R B B B O O O
+      Y Y Y
+      Y Y Y
+      Y Y Y
+";
+
+#[test]
+fn test_ascii_net() {
+    let scheme = RubikColor::classic_map();
+    assert_eq!(net(&Rubik::new(), &scheme, Style::Ascii), SOLVED);
+
+    // U brings the R stickers of the top row onto F, and the F ones onto L
+    let rubik = scrambled(tf!(U));
+    let text = net(&rubik, &scheme, Style::Ascii);
+    assert_eq!(text.lines().nth(3), Some("R R R B B B O O O G G G"));
+    assert_eq!(text.lines().nth(4), Some("G G G R R R B B B O O O"));
+    assert_eq!(rubik.to_string(), text);
+}
+
+#[test]
+fn test_styles() {
+    let scheme = RubikColor::classic_map();
+    let rubik = scrambled(tf!(R, U, FI));
+    for style in [Style::Ascii, Style::Ansi, Style::Unicode] {
+        let text = net(&rubik, &scheme, style);
+        assert_eq!(text.lines().count(), 9);
+        assert!(text.ends_with('\n'));
+        // only colors, no cursor movement nor screen clearing
+        let escapes = text.matches('\x1b').count();
+        assert_eq!(text.matches("\x1b[48;2;").count() * 2, escapes);
+        assert_eq!(text.matches("\x1b[0m").count() * 2, escapes);
+    }
+
+    let ansi = net(&Rubik::new(), &scheme, Style::Ansi);
+    assert_eq!(ansi.matches("\x1b[48;2;255;255;255m  \x1b[0m").count(), 9);
+    assert!(ansi.starts_with("      \x1b["));
+
+    let unicode = net(&Rubik::new(), &scheme, Style::Unicode);
+    assert_eq!(unicode.lines().next(), Some("      ⬜⬜⬜"));
+    assert_eq!(unicode.lines().nth(4), Some("🟩🟩🟩🟥🟥🟥🟦🟦🟦🟧🟧🟧"));
+
+    // the scheme gives the colors
+    let scheme = CubeFaceMap::new(
+        RubikColor::Green,
+        RubikColor::Red,
+        RubikColor::Yellow,
+        RubikColor::Blue,
+        RubikColor::Orange,
+        RubikColor::White,
+    );
+    let text = net(&Rubik::new(), &scheme, Style::Ascii);
+    assert_eq!(text.lines().next(), Some("      Y Y Y"));
+    assert_eq!(text.lines().nth(3), Some("O O O G G G R R R B B B"));
+}
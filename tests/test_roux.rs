@@ -0,0 +1,70 @@
+use rubik::{
+    prelude::*,
+    solver::{
+        last_layer::{LlSet, LlState},
+        roux::{RouxSolver, CMLL, LSE_MOVES},
+        steps::invert,
+        RubikSolver,
+    },
+    tf,
+};
+
+#[test]
+fn test_roux_solves() {
+    let mut rubik = Rubik::new();
+    rubik.execute(&tf!(R, U, RI, UI, F2, D, LI, B, R2, UI));
+    let steps = RouxSolver.steps(rubik.clone());
+    let names = steps
+        .iter()
+        .map(|step| step.name.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        names,
+        [
+            "first block",
+            "second block",
+            "CMLL",
+            "LSE: edge orientation",
+            "LSE: UL and UR",
+            "LSE: edge permutation"
+        ]
+    );
+    for step in &steps[3..] {
+        assert!(step.ops.iter().all(|op| LSE_MOVES.contains(op)));
+    }
+    // slice moves are kept as such
+    let lse = steps[3..]
+        .iter()
+        .map(|step| step.notation())
+        .collect::<Vec<_>>()
+        .join(" ");
+    assert!(lse.split(' ').any(|op| op.starts_with('M')));
+    assert!(!lse.contains('R') && !lse.contains('L'));
+
+    let (solved, ops) = RouxSolver.solve(rubik).collect();
+    assert!(solved.is_solved());
+    assert_eq!(
+        ops.len(),
+        steps.iter().map(|step| step.ops.len()).sum::<usize>()
+    );
+}
+
+#[test]
+fn test_cmll() {
+    // one algorithm for each COLL case, named after it but for the swaps of oriented corners
+    let coll = LlSet::Coll.named_cases();
+    assert_eq!(CMLL.len(), coll.len());
+    for (alg, (name, case)) in CMLL.iter().zip(&coll) {
+        let name = name
+            .replace("COLL", "CMLL")
+            .replace("PLL 2", "O adjacent")
+            .replace("PLL 3", "O diagonal");
+        assert_eq!(alg.name, name);
+        let mut rubik = Rubik::new();
+        invert(alg.moves)
+            .iter()
+            .for_each(|op| op.apply_on(&mut rubik));
+        let state = LlState::new(&rubik).expect("the first two layers are kept");
+        assert_eq!(state.coll_key(), case.key, "{}", alg.name);
+    }
+}
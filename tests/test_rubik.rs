@@ -1,9 +1,41 @@
 use rubik::{prelude::*, tf};
 mod print_rubik;
 use print_rubik::print_rubik;
+mod common;
+use common::scrambled::scrambled;
 #[test]
 pub fn test_rubik() {
     let mut rubik = Rubik::new();
     print_rubik(rubik.execute(&tf!((R, U, RI, UI, RI, F, R, FI); 10)));
     print_rubik(rubik.execute(&tf!(F, U, R, UI, RI, FI)));
 }
+
+#[test]
+fn test_compose_states() {
+    let a = tf!(R, U, FI, M, D2, SI, L);
+    let b = tf!(E, B, RI, U2, F, L2);
+    assert_eq!(
+        scrambled(a.clone()).compose(&scrambled(b.clone())),
+        scrambled(tf!(a.clone(), b))
+    );
+    assert_eq!(
+        scrambled(a.clone()).inverse(),
+        scrambled(a.clone().inverse())
+    );
+    assert_eq!(
+        scrambled(a.clone()).compose(&scrambled(a).inverse()),
+        Rubik::new()
+    );
+}
+
+#[test]
+fn test_normalized() {
+    // every piece goes back, the U center turned half around
+    let rubik = scrambled(tf!((U, R, L, U2, RI, LI); 2));
+    assert_ne!(rubik, Rubik::new());
+    assert_eq!(rubik.normalized(), Rubik::new());
+    // the centers M2 spins go back, the edges it swaps stay swapped
+    let slice = scrambled(tf!(M2)).normalized();
+    assert_ne!(slice, Rubik::new());
+    assert_eq!(slice.compose(&slice), Rubik::new());
+}
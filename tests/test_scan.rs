@@ -0,0 +1,64 @@
+use rubik::{
+    prelude::*,
+    scan::{classify, Rgb},
+    tf,
+};
+
+mod common;
+use common::scrambled::scrambled;
+
+/// the colors of the stickers of `rubik` under the classic scheme, shifted a little
+/// differently for each sticker and scaled by `light`
+fn photo(rubik: &Rubik, light: f64) -> [Rgb; 54] {
+    let scheme = RubikColor::classic_map();
+    std::array::from_fn(|index| {
+        let facelet = Facelet::from_index(index).expect("a facelet");
+        let rgb = scheme[rubik.facelet(facelet)].rgb();
+        rgb.map(|channel| {
+            let noise = (index * 37 % 21) as f64 - 10.0;
+            ((channel as f64 + noise) * light).clamp(0.0, 255.0) as u8
+        })
+    })
+}
+
+#[test]
+fn test_classify() {
+    let scheme = RubikColor::classic_map();
+    let rubik = scrambled(tf!(R, U, FI, L2, D, B, RI));
+    for light in [1.0, 0.6] {
+        let scan = classify(&photo(&rubik, light));
+        for facelet in Facelet::all() {
+            assert_eq!(
+                scan.colors[facelet.index()],
+                scheme[rubik.facelet(facelet)],
+                "{facelet}"
+            );
+        }
+        assert!(scan.ambiguous(0.3).is_empty());
+        let read = scan.rubik(&scheme).expect("a state");
+        assert!(Facelet::all().all(|facelet| read.facelet(facelet) == rubik.facelet(facelet)));
+    }
+}
+
+#[test]
+fn test_ambiguous() {
+    let scheme = RubikColor::classic_map();
+    let mut rgb = photo(&Rubik::new(), 1.0);
+    // two red stickers looking orange, one more than the other
+    let [red, orange] = [RubikColor::Red, RubikColor::Orange].map(RubikColor::rgb);
+    let mix = |part: f64| -> Rgb {
+        std::array::from_fn(|i| (red[i] as f64 * (1.0 - part) + orange[i] as f64 * part) as u8)
+    };
+    let f1 = Facelet::new(CubeFace::F, 0, 0).expect("a facelet");
+    let f9 = Facelet::new(CubeFace::F, 2, 2).expect("a facelet");
+    rgb[f1.index()] = mix(0.7);
+    rgb[f9.index()] = mix(0.5);
+    let scan = classify(&rgb);
+    // with nine stickers per color, both still read red
+    assert_eq!(scan.colors[f1.index()], RubikColor::Red);
+    assert_eq!(scan.colors[f9.index()], RubikColor::Red);
+    assert_eq!(scan.ambiguous(0.3), [f1, f9]);
+    assert_eq!(scan.confidence[f1.index()], 0.0);
+    assert_eq!(scan.confidence[4], 1.0);
+    assert!(scan.rubik(&scheme).expect("a state").is_solved());
+}
@@ -0,0 +1,76 @@
+use rubik::{prelude::*, render::svg, tf};
+
+mod common;
+use common::scrambled::scrambled;
+
+fn classic() -> CubeFaceMap<[u8; 3]> {
+    RubikColor::classic_map().map(|color| color.rgb())
+}
+
+fn standalone(svg: &str) -> bool {
+    svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\"") && svg.ends_with("</svg>\n")
+}
+
+#[test]
+fn test_net() {
+    let svg = svg::net(&Rubik::new(), &classic());
+    assert!(standalone(&svg));
+    assert_eq!(svg.matches("<rect").count(), 54);
+    assert_eq!(svg.matches("fill=\"#ffffff\"").count(), 9);
+    assert_eq!(svg.matches("fill=\"#c41e3a\"").count(), 9);
+
+    // custom colors
+    let colors = CubeFaceMap::new(
+        [1, 2, 3],
+        [4, 5, 6],
+        [7, 8, 9],
+        [10, 11, 12],
+        [13, 14, 15],
+        [16, 17, 18],
+    );
+    let svg = svg::net(&scrambled(tf!(R)), &colors);
+    assert_eq!(svg.matches("fill=\"#070809\"").count(), 9);
+    // the first sticker drawn is the top left one of U
+    assert!(svg.contains(
+        "<rect x=\"100.00\" y=\"10.00\" width=\"30.00\" height=\"30.00\" fill=\"#070809\""
+    ));
+}
+
+#[test]
+fn test_isometric() {
+    let svg = svg::isometric(&Rubik::new(), &classic());
+    assert!(standalone(&svg));
+    assert_eq!(svg.matches("<polygon").count(), 27);
+    for color in ["#ffffff", "#c41e3a", "#0051ba"] {
+        assert_eq!(svg.matches(&format!("fill=\"{color}\"")).count(), 9);
+    }
+    // F brings L stickers onto U and U ones onto R, hiding three R stickers under D
+    let svg = svg::isometric(&scrambled(tf!(F)), &classic());
+    assert_eq!(svg.matches("fill=\"#009e60\"").count(), 3);
+    assert_eq!(svg.matches("fill=\"#ffffff\"").count(), 9);
+    assert_eq!(svg.matches("fill=\"#0051ba\"").count(), 6);
+}
+
+#[test]
+fn test_last_layer() {
+    let solved = svg::last_layer(&Rubik::new(), &classic(), true);
+    assert!(standalone(&solved));
+    assert_eq!(solved.matches("<rect").count(), 21);
+    assert_eq!(solved.matches("fill=\"#ffffff\"").count(), 9);
+    assert_eq!(solved.matches("fill=\"#c41e3a\"").count(), 3);
+    assert!(!solved.contains("<line"));
+
+    // the T permutation swaps UL with UR and UFR with UBR
+    let t_perm = scrambled(tf!(R, U, RI, UI, RI, F, R2, UI, RI, UI, R, U, RI, FI));
+    let svg = svg::last_layer(&t_perm, &classic(), true);
+    assert_eq!(svg.matches("<line").count(), 2);
+    assert_eq!(svg.matches("marker-start").count(), 2);
+    assert!(svg.contains("y1=\"68.00\" x2=\"90.50\" y2=\"68.00\""));
+    assert!(!svg::last_layer(&t_perm, &classic(), false).contains("<line"));
+
+    // the U permutation cycles three edges
+    let u_perm = scrambled(tf!(R, UI, R, U, R, U, R, UI, RI, UI, R2));
+    let svg = svg::last_layer(&u_perm, &classic(), true);
+    assert_eq!(svg.matches("<line").count(), 3);
+    assert!(!svg.contains("marker-start"));
+}
@@ -0,0 +1,143 @@
+use std::path::PathBuf;
+
+use rubik::tables::{pruning_table, Table, TableCache, TableError, TableSpec, HEADER_LEN};
+
+struct Squares;
+
+impl TableSpec for Squares {
+    const NAME: &'static str = "squares";
+    const VERSION: u32 = 1;
+    fn generate() -> Vec<u8> {
+        (0..=255u8).map(|x| x.wrapping_mul(x)).collect()
+    }
+}
+
+struct SquaresV2;
+
+impl TableSpec for SquaresV2 {
+    const NAME: &'static str = "squares";
+    const VERSION: u32 = 2;
+    fn generate() -> Vec<u8> {
+        (0..=255u8).rev().collect()
+    }
+}
+
+fn cache_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("rubik-test-tables-{name}-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    dir
+}
+
+#[test]
+fn test_save_load() {
+    let cache = TableCache::new(cache_dir("save-load"));
+    let table = Table::generate::<Squares>();
+    assert!(table.verify().is_ok());
+    table.save(cache.path_of::<Squares>()).unwrap();
+    let loaded = cache.load::<Squares>().unwrap();
+    assert_eq!(&loaded[..], &table[..]);
+    assert_eq!(loaded.header(), table.header());
+    assert!(matches!(
+        cache.load::<SquaresV2>(),
+        Err(TableError::Stale { .. })
+    ));
+}
+
+#[test]
+fn test_regenerate() {
+    let cache = TableCache::new(cache_dir("regenerate"));
+    assert!(matches!(cache.load::<Squares>(), Err(TableError::Io(_))));
+    let table = cache.load_or_generate::<Squares>();
+    assert_eq!(table[3], 9);
+
+    // corrupt one byte of the payload
+    let path = cache.path_of::<Squares>();
+    let mut bytes = std::fs::read(&path).unwrap();
+    bytes[HEADER_LEN + 3] = 0;
+    std::fs::write(&path, bytes).unwrap();
+    assert!(matches!(
+        cache.load::<Squares>(),
+        Err(TableError::Checksum { .. })
+    ));
+    assert_eq!(cache.load_or_generate::<Squares>()[3], 9);
+
+    // a new table version replaces the old file
+    assert_eq!(cache.load_or_generate::<SquaresV2>()[0], 255);
+    assert!(cache.load::<Squares>().is_err());
+}
+
+#[test]
+fn test_read_errors() {
+    let cache = TableCache::new(cache_dir("read-errors"));
+    let path = cache.path_of::<Squares>();
+    std::fs::create_dir_all(&cache.dir).unwrap();
+
+    std::fs::write(&path, b"not a table").unwrap();
+    assert!(matches!(cache.load::<Squares>(), Err(TableError::BadMagic)));
+
+    // a header cut short is truncated, not foreign
+    let table = Table::generate::<Squares>();
+    table.save(&path).unwrap();
+    let bytes = std::fs::read(&path).unwrap();
+    std::fs::write(&path, &bytes[..HEADER_LEN - 4]).unwrap();
+    assert!(matches!(
+        cache.load::<Squares>(),
+        Err(TableError::Truncated { found, expected }) if found == HEADER_LEN as u64 - 4 && expected == HEADER_LEN as u64
+    ));
+    std::fs::write(&path, &bytes[..HEADER_LEN + 10]).unwrap();
+    assert!(matches!(
+        cache.load::<Squares>(),
+        Err(TableError::Truncated {
+            found: 10,
+            expected: 256
+        })
+    ));
+
+    // a header claiming more bytes than the file holds fails before allocating them
+    let mut header = table.header().to_bytes();
+    header[12..20].copy_from_slice(&u64::MAX.to_le_bytes());
+    std::fs::write(&path, [&header[..], &bytes[HEADER_LEN..]].concat()).unwrap();
+    assert!(matches!(
+        cache.load::<Squares>(),
+        Err(TableError::Truncated {
+            found: 256,
+            expected: u64::MAX
+        })
+    ));
+    assert_eq!(cache.load_or_generate::<Squares>()[3], 9);
+
+    // a directory where the table should be cannot be read, nor replaced
+    std::fs::remove_file(&path).unwrap();
+    std::fs::create_dir_all(&path).unwrap();
+    assert!(matches!(cache.load::<Squares>(), Err(TableError::Io(_))));
+    let (table, saved) = cache.regenerate::<Squares>();
+    assert_eq!(table[3], 9);
+    assert!(matches!(saved, Err(TableError::Io(_))));
+    assert_eq!(cache.load_or_generate::<Squares>()[3], 9);
+}
+
+#[cfg(feature = "mmap")]
+#[test]
+fn test_mmap() {
+    let mut cache = TableCache::new(cache_dir("mmap"));
+    cache.mmap = true;
+    let table = cache.load_or_generate::<Squares>();
+    assert!(table.is_mapped());
+    assert_eq!(&table[..], &Table::generate::<Squares>()[..]);
+}
+
+#[test]
+fn test_pruning_table() {
+    // distance on a cycle of 10 states
+    let table = pruning_table(10, [0], |x| [(x + 1) % 10, (x + 9) % 10]).unwrap();
+    assert_eq!(table, vec![0, 1, 2, 3, 4, 5, 4, 3, 2, 1]);
+    // the end of a path of 256 states is further than a byte can tell
+    assert!(matches!(
+        pruning_table(256, [0], |x| [(x + 1).min(255)]),
+        Err(TableError::TooDeep)
+    ));
+    assert_eq!(
+        pruning_table(255, [0], |x| [(x + 1).min(254)]).unwrap()[254],
+        254
+    );
+}
@@ -0,0 +1,69 @@
+use rubik::{
+    prelude::*,
+    solver::{
+        enumerate::EnumerateSolver,
+        mask::GoalMask,
+        zz::{EoCrossSolver, EoLineSolver, RUL_MOVES},
+        RubikMultiSolver, RubikSolver,
+    },
+    tf,
+};
+
+mod common;
+use common::scrambled::scrambled;
+
+#[test]
+fn test_bad_edges() {
+    for (axis, keep, flip) in [
+        (CubeFace::F, tf!(R, U, L, D, F2, B2), RubikLayerTransform::F),
+        (CubeFace::L, tf!(F, U, B, D, R2, L2), RubikLayerTransform::R),
+        (CubeFace::U, tf!(F, R, B, L, U2, D2), RubikLayerTransform::D),
+    ] {
+        let mut rubik = scrambled(keep);
+        assert_eq!(rubik.bad_edge_count(axis), 0, "{axis:?}");
+        flip.apply_on(&mut rubik);
+        assert_eq!(rubik.bad_edge_count(axis), 4, "{axis:?}");
+    }
+    // the F/B axis agrees with the orientation of the goal masks
+    let rubik = scrambled(tf!(F, R, BI, U));
+    let oriented = GoalMask::new().orient(CubePosition::all().filter(|p| p.is_edge()));
+    assert!(rubik.bad_edge_count(CubeFace::F) > 0);
+    assert!(!oriented.is_reached(&rubik));
+    assert_eq!(
+        rubik.bad_edges(CubeFace::F).collect::<Vec<_>>(),
+        rubik.bad_edges(CubeFace::B).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn test_eo_line() {
+    let rubik = scrambled(tf!(F, R, D2, BI, U, L));
+    let (solved, ops) = EoLineSolver.solve(rubik.clone()).collect();
+    assert_eq!(solved.bad_edge_count(CubeFace::F), 0);
+    assert!(GoalMask::eo_line(CubeFace::D).is_reached(&solved));
+    // optimal
+    let brute = EnumerateSolver::htm(ops.len())
+        .with_goal(GoalMask::eo_line(CubeFace::D))
+        .solutions(rubik.clone())
+        .next()
+        .unwrap();
+    assert_eq!(brute.depth(), ops.len());
+
+    // the first two layers can then be solved without breaking the orientation
+    let mut rubik = solved;
+    for op in RUL_MOVES.iter().cycle().step_by(4).take(20) {
+        op.apply_on(&mut rubik);
+        assert_eq!(rubik.bad_edge_count(CubeFace::F), 0);
+    }
+}
+
+#[test]
+fn test_eo_cross() {
+    let rubik = scrambled(tf!(F, R, D2, BI, U, L));
+    let mut solutions = EoCrossSolver.solutions(rubik);
+    let first = solutions.next().unwrap();
+    assert!(GoalMask::eo_cross(CubeFace::D).is_reached(first.rubik()));
+    assert_eq!(first.rubik().bad_edge_count(CubeFace::F), 0);
+    let second = solutions.next().unwrap();
+    assert!(second.depth() >= first.depth());
+}